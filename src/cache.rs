@@ -0,0 +1,116 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{Method, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+/// An opt-in, on-disk cache for GET responses, keyed by a hash of the
+/// method and URL. Entries are revalidated with conditional requests
+/// (`If-None-Match`/`If-Modified-Since`) rather than trusted blindly, so a
+/// TTL is only needed to bound how long a stale entry is retried at all.
+pub(crate) struct Cache {
+    dir: PathBuf,
+    ttl: Option<Duration>,
+}
+
+pub(crate) struct Entry {
+    pub(crate) content_type: Option<String>,
+    pub(crate) etag: Option<String>,
+    pub(crate) last_modified: Option<String>,
+    pub(crate) body: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    stored_at: u64,
+}
+
+impl Cache {
+    pub(crate) fn new(ttl: Option<Duration>) -> Result<Self, Error> {
+        let dir = cache_dir()?;
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, ttl })
+    }
+
+    pub(crate) fn get(&self, method: &Method, url: &Url) -> Option<Entry> {
+        let key = cache_key(method, url);
+        let raw = fs::read(self.meta_path(&key)).ok()?;
+        let meta: Metadata = serde_json::from_slice(&raw).ok()?;
+
+        if let Some(ttl) = self.ttl {
+            let stored_at = UNIX_EPOCH + Duration::from_secs(meta.stored_at);
+            if SystemTime::now().duration_since(stored_at).unwrap_or_default() > ttl {
+                return None;
+            }
+        }
+
+        let body = fs::read(self.body_path(&key)).ok()?;
+        Some(Entry {
+            content_type: meta.content_type,
+            etag: meta.etag,
+            last_modified: meta.last_modified,
+            body,
+        })
+    }
+
+    pub(crate) fn store(
+        &self,
+        method: &Method,
+        url: &Url,
+        content_type: Option<&str>,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+        body: &[u8],
+    ) -> std::io::Result<()> {
+        let key = cache_key(method, url);
+        let meta = Metadata {
+            content_type: content_type.map(str::to_string),
+            etag: etag.map(str::to_string),
+            last_modified: last_modified.map(str::to_string),
+            stored_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        fs::write(self.meta_path(&key), serde_json::to_vec(&meta).unwrap())?;
+        fs::write(self.body_path(&key), body)
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn body_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.body"))
+    }
+}
+
+fn cache_key(method: &Method, url: &Url) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(url.as_str().as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn cache_dir() -> Result<PathBuf, Error> {
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+        return Ok(PathBuf::from(dir).join("fetch"));
+    }
+    let home = env::var_os("HOME")
+        .ok_or_else(|| Error::new("cache: unable to determine a cache directory"))?;
+    Ok(PathBuf::from(home).join(".cache").join("fetch"))
+}
@@ -0,0 +1,132 @@
+/// A small set of text encodings that can be transcoded to UTF-8 without
+/// pulling in a dedicated WHATWG-encoding crate. `Utf8` is the common case
+/// and is a pass-through (aside from a lossy-replacement fallback); the
+/// single-byte encodings are decoded via a fixed lookup table.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Charset {
+    Utf8,
+    Windows1252,
+}
+
+impl Charset {
+    // Resolves a charset label to a `Charset`, following the WHATWG
+    // encoding spec's alias list for the handful of labels supported here.
+    // Per that spec, every ISO-8859-1 alias is also decoded as
+    // Windows-1252 (the web-compat quirk where content labeled
+    // "ISO-8859-1" is actually decoded with the Windows-1252 0x80-0x9F
+    // substitutions). See https://encoding.spec.whatwg.org/#names-and-labels.
+    fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" | "unicode-1-1-utf-8" => Some(Charset::Utf8),
+            "windows-1252" | "cp1252" | "x-cp1252" | "ansi_x3.4-1968" | "ascii" | "us-ascii"
+            | "iso-8859-1" | "iso8859-1" | "latin1" | "l1" | "cp819" => Some(Charset::Windows1252),
+            _ => None,
+        }
+    }
+}
+
+// Parses the `charset` parameter out of a `Content-Type` header value,
+// e.g. `text/html; charset=iso-8859-1`.
+pub(crate) fn from_content_type(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, val) = param.split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("charset") {
+            Some(val.trim().trim_matches('"'))
+        } else {
+            None
+        }
+    })
+}
+
+/// Transcodes `input` to UTF-8, given a charset label (from `--charset` or
+/// the response's `Content-Type`). Labels outside the small set this module
+/// implements a real table for fall back to lossy UTF-8 (with a warning),
+/// the same as before charset-awareness was added, rather than failing the
+/// whole request over a body that would otherwise have rendered fine.
+pub(crate) fn decode(input: &[u8], label: &str) -> Vec<u8> {
+    match Charset::from_label(label) {
+        Some(Charset::Utf8) => String::from_utf8_lossy(input).into_owned().into_bytes(),
+        Some(Charset::Windows1252) => decode_windows_1252(input),
+        None => {
+            eprintln!("warning: charset '{label}' is not supported, decoding as UTF-8");
+            String::from_utf8_lossy(input).into_owned().into_bytes()
+        }
+    }
+}
+
+// Windows-1252 matches ISO-8859-1 except for the 0x80-0x9F range, which it
+// assigns to various printable characters rather than C1 controls.
+fn decode_windows_1252(input: &[u8]) -> Vec<u8> {
+    input
+        .iter()
+        .map(|&b| match b {
+            0x80 => '\u{20AC}',
+            0x82 => '\u{201A}',
+            0x83 => '\u{0192}',
+            0x84 => '\u{201E}',
+            0x85 => '\u{2026}',
+            0x86 => '\u{2020}',
+            0x87 => '\u{2021}',
+            0x88 => '\u{02C6}',
+            0x89 => '\u{2030}',
+            0x8A => '\u{0160}',
+            0x8B => '\u{2039}',
+            0x8C => '\u{0152}',
+            0x8E => '\u{017D}',
+            0x91 => '\u{2018}',
+            0x92 => '\u{2019}',
+            0x93 => '\u{201C}',
+            0x94 => '\u{201D}',
+            0x95 => '\u{2022}',
+            0x96 => '\u{2013}',
+            0x97 => '\u{2014}',
+            0x98 => '\u{02DC}',
+            0x99 => '\u{2122}',
+            0x9A => '\u{0161}',
+            0x9B => '\u{203A}',
+            0x9C => '\u{0153}',
+            0x9E => '\u{017E}',
+            0x9F => '\u{0178}',
+            other => other as char,
+        })
+        .collect::<String>()
+        .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_content_type() {
+        assert_eq!(
+            from_content_type("text/html; charset=iso-8859-1"),
+            Some("iso-8859-1")
+        );
+        assert_eq!(from_content_type("application/json"), None);
+    }
+
+    #[test]
+    fn test_decode_iso_8859_1() {
+        let decoded = decode(&[0xe9], "iso-8859-1");
+        assert_eq!(String::from_utf8(decoded).unwrap(), "\u{e9}");
+    }
+
+    #[test]
+    fn test_decode_iso_8859_1_uses_windows_1252_substitutions() {
+        // Per the WHATWG spec, content labeled iso-8859-1 is actually
+        // decoded as windows-1252, so 0x93 is a curly quote, not a C1
+        // control character.
+        let decoded = decode(&[0x93], "iso-8859-1");
+        assert_eq!(String::from_utf8(decoded).unwrap(), "\u{201C}");
+    }
+
+    #[test]
+    fn test_decode_unsupported_falls_back_to_lossy_utf8() {
+        // An unrecognized/unimplemented label shouldn't fail the whole
+        // request; it should render as lossy UTF-8, same as before
+        // charset-awareness existed.
+        let decoded = decode(b"hello", "shift_jis");
+        assert_eq!(String::from_utf8(decoded).unwrap(), "hello");
+    }
+}
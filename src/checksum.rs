@@ -0,0 +1,92 @@
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::error::Error;
+
+/// A parsed `--checksum algo:hex` value, checked against a digest computed
+/// over whatever bytes `fetch` ends up writing to `--output`.
+pub(crate) struct Checksum {
+    algo: Algo,
+    expected: String,
+}
+
+#[derive(Copy, Clone)]
+enum Algo {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Checksum {
+    pub(crate) fn parse(s: &str) -> Result<Self, Error> {
+        let (algo, hex) = s
+            .split_once(':')
+            .ok_or_else(|| Error::new("checksum: format must be 'ALGO:HEX'"))?;
+        let algo = match algo.to_ascii_lowercase().as_str() {
+            "sha1" => Algo::Sha1,
+            "sha256" => Algo::Sha256,
+            "sha512" => Algo::Sha512,
+            other => {
+                return Err(Error::new(format!(
+                    "checksum: unsupported algorithm '{other}' (expected sha1, sha256 or sha512)"
+                )))
+            }
+        };
+        if hex.is_empty() {
+            return Err(Error::new("checksum: missing expected digest"));
+        }
+
+        Ok(Self {
+            algo,
+            expected: hex.to_ascii_lowercase(),
+        })
+    }
+
+    pub(crate) fn hasher(&self) -> Hasher {
+        match self.algo {
+            Algo::Sha1 => Hasher::Sha1(Sha1::new()),
+            Algo::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algo::Sha512 => Hasher::Sha512(Sha512::new()),
+        }
+    }
+
+    pub(crate) fn verify(&self, digest: &str) -> Result<(), Error> {
+        if digest == self.expected {
+            Ok(())
+        } else {
+            Err(Error::new(format!(
+                "checksum mismatch: expected {}, got {digest}",
+                self.expected
+            )))
+        }
+    }
+}
+
+/// A digest in progress, fed bytes as they're read via [`crate::progress::HashingReader`].
+pub(crate) enum Hasher {
+    Sha1(Sha1),
+    Sha256(Sha256),
+    Sha512(Sha512),
+}
+
+impl Hasher {
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+        }
+    }
+
+    pub(crate) fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Sha1(h) => hex(&h.finalize()),
+            Hasher::Sha256(h) => hex(&h.finalize()),
+            Hasher::Sha512(h) => hex(&h.finalize()),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
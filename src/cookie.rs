@@ -0,0 +1,294 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jiff::fmt::rfc2822;
+use reqwest::Url;
+
+use crate::error::Error;
+
+#[derive(Clone, Debug)]
+struct Cookie {
+    domain: String,
+    include_subdomains: bool,
+    path: String,
+    secure: bool,
+    http_only: bool,
+    expires: Option<i64>,
+    name: String,
+    value: String,
+}
+
+/// A cookie jar backed by a Netscape-format `cookies.txt` file (the same
+/// format curl/wget use), so a jar can be shared with other tools. Cookies
+/// are matched against outgoing requests by domain/path/secure/expiry, and
+/// `Set-Cookie` headers from a response are merged back in and persisted.
+pub(crate) struct CookieJar {
+    path: PathBuf,
+    cookies: Vec<Cookie>,
+}
+
+impl CookieJar {
+    pub(crate) fn load(path: &str) -> Result<Self, Error> {
+        let path = PathBuf::from(path);
+        let cookies = match fs::read_to_string(&path) {
+            Ok(raw) => parse_netscape(&raw),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, cookies })
+    }
+
+    // Builds the `Cookie` request header value for cookies that apply to
+    // `url`, or None if none apply.
+    pub(crate) fn header_for(&self, url: &Url) -> Option<String> {
+        let host = url.host_str()?;
+        let request_path = url.path();
+        let secure = url.scheme() == "https";
+        let now = now_unix();
+
+        let matching: Vec<&Cookie> = self
+            .cookies
+            .iter()
+            .filter(|c| domain_matches(c, host))
+            .filter(|c| path_matches(&c.path, request_path))
+            .filter(|c| !c.secure || secure)
+            .filter(|c| c.expires.map_or(true, |exp| exp > now))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        Some(
+            matching
+                .iter()
+                .map(|c| format!("{}={}", c.name, c.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    // Parses every `Set-Cookie` value from a response and merges each into
+    // the jar, replacing any existing cookie with the same name/domain/path.
+    pub(crate) fn merge_set_cookie<'a>(&mut self, url: &Url, values: impl Iterator<Item = &'a str>) {
+        for raw in values {
+            if let Some(cookie) = parse_set_cookie(url, raw) {
+                self.cookies.retain(|c| {
+                    !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path)
+                });
+                // An expiry in the past is how a server deletes a cookie.
+                if cookie.expires.map_or(true, |exp| exp > now_unix()) {
+                    self.cookies.push(cookie);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn save(&self) -> io::Result<()> {
+        let mut out = String::from("# Netscape HTTP Cookie File\n");
+        for c in &self.cookies {
+            let domain = if c.http_only {
+                format!("#HttpOnly_{}", c.domain)
+            } else {
+                c.domain.clone()
+            };
+            out.push_str(&format!(
+                "{domain}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                bool_field(c.include_subdomains),
+                c.path,
+                bool_field(c.secure),
+                c.expires.unwrap_or(0),
+                c.name,
+                c.value,
+            ));
+        }
+        fs::write(&self.path, out)
+    }
+}
+
+fn bool_field(v: bool) -> &'static str {
+    if v {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
+
+fn domain_matches(cookie: &Cookie, host: &str) -> bool {
+    if cookie.include_subdomains {
+        host == cookie.domain || host.ends_with(&format!(".{}", cookie.domain))
+    } else {
+        host == cookie.domain
+    }
+}
+
+fn path_matches(cookie_path: &str, request_path: &str) -> bool {
+    request_path == cookie_path
+        || request_path.starts_with(&format!("{}/", cookie_path.trim_end_matches('/')))
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+// Parses the Netscape `cookies.txt` format: tab-separated fields of
+// `domain, include_subdomains, path, secure, expires, name, value`. A
+// `#HttpOnly_` prefix on the domain (curl's convention) marks an HttpOnly
+// cookie; any other line starting with `#` is a comment.
+fn parse_netscape(raw: &str) -> Vec<Cookie> {
+    raw.lines()
+        .filter_map(|line| {
+            let (http_only, line) = match line.strip_prefix("#HttpOnly_") {
+                Some(rest) => (true, rest),
+                None => {
+                    if line.trim().is_empty() || line.starts_with('#') {
+                        return None;
+                    }
+                    (false, line)
+                }
+            };
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 7 {
+                return None;
+            }
+            Some(Cookie {
+                domain: fields[0].to_string(),
+                include_subdomains: fields[1] == "TRUE",
+                path: fields[2].to_string(),
+                secure: fields[3] == "TRUE",
+                http_only,
+                expires: fields[4].parse().ok().filter(|&v| v != 0),
+                name: fields[5].to_string(),
+                value: fields[6].to_string(),
+            })
+        })
+        .collect()
+}
+
+// Parses a single `Set-Cookie` header value, defaulting Domain/Path from
+// the request URL when the server doesn't specify them.
+fn parse_set_cookie(url: &Url, raw: &str) -> Option<Cookie> {
+    let mut parts = raw.split(';');
+    let (name, value) = parts.next()?.trim().split_once('=')?;
+
+    let host = url.host_str()?;
+    let mut domain = host.to_string();
+    let mut include_subdomains = false;
+    let mut path = default_path(url.path());
+    let mut secure = false;
+    let mut http_only = false;
+    let mut expires: Option<i64> = None;
+
+    for attr in parts {
+        let attr = attr.trim();
+        if attr.eq_ignore_ascii_case("secure") {
+            secure = true;
+            continue;
+        }
+        if attr.eq_ignore_ascii_case("httponly") {
+            http_only = true;
+            continue;
+        }
+        let Some((key, val)) = attr.split_once('=') else {
+            continue;
+        };
+        let val = val.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "domain" => {
+                let declared = val.trim_start_matches('.');
+                // RFC 6265 domain-matching: only accept the attribute if
+                // it's the request host itself or a proper superdomain of
+                // it; otherwise ignore it and fall back to host-only, so a
+                // response can't plant a cookie for an unrelated domain.
+                if declared.eq_ignore_ascii_case(host)
+                    || host.to_ascii_lowercase().ends_with(&format!(".{}", declared.to_ascii_lowercase()))
+                {
+                    domain = declared.to_string();
+                    include_subdomains = true;
+                }
+            }
+            "path" => path = val.to_string(),
+            "max-age" => expires = val.parse::<i64>().ok().map(|secs| now_unix() + secs),
+            "expires" => expires = parse_cookie_date(val),
+            _ => {}
+        }
+    }
+
+    Some(Cookie {
+        domain,
+        include_subdomains,
+        path,
+        secure,
+        http_only,
+        expires,
+        name: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(idx) => request_path[..idx].to_string(),
+    }
+}
+
+// Cookie dates are RFC 2822-ish but commonly use dashes between the day,
+// month and year (`Wdy, DD-Mon-YYYY HH:MM:SS GMT`); try the literal value
+// first and fall back to normalizing those dashes to spaces.
+fn parse_cookie_date(value: &str) -> Option<i64> {
+    rfc2822::parse(value)
+        .or_else(|_| rfc2822::parse(&value.replacen('-', " ", 2).replacen('-', " ", 1)))
+        .ok()
+        .map(|zoned| zoned.timestamp().as_second())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_netscape_and_match() {
+        let raw = "# Netscape HTTP Cookie File\nexample.com\tFALSE\t/\tFALSE\t0\tsession\tabc123\n";
+        let jar = CookieJar {
+            path: PathBuf::new(),
+            cookies: parse_netscape(raw),
+        };
+        let url = Url::parse("http://example.com/path").unwrap();
+        assert_eq!(jar.header_for(&url), Some("session=abc123".to_string()));
+    }
+
+    #[test]
+    fn test_merge_set_cookie() {
+        let mut jar = CookieJar {
+            path: PathBuf::new(),
+            cookies: Vec::new(),
+        };
+        let url = Url::parse("https://example.com/login").unwrap();
+        jar.merge_set_cookie(&url, std::iter::once("id=1; Path=/; Secure; HttpOnly"));
+        assert_eq!(jar.header_for(&url), Some("id=1".to_string()));
+
+        let insecure = Url::parse("http://example.com/login").unwrap();
+        assert_eq!(jar.header_for(&insecure), None);
+    }
+
+    #[test]
+    fn test_set_cookie_rejects_unrelated_domain() {
+        let mut jar = CookieJar {
+            path: PathBuf::new(),
+            cookies: Vec::new(),
+        };
+        let url = Url::parse("https://evil.example/").unwrap();
+        jar.merge_set_cookie(&url, std::iter::once("sid=x; Domain=example.com"));
+
+        // The bogus Domain attribute is ignored, falling back to a
+        // host-only cookie, so it doesn't leak to an unrelated domain.
+        let other = Url::parse("https://example.com/").unwrap();
+        assert_eq!(jar.header_for(&other), None);
+        assert_eq!(jar.header_for(&url), Some("sid=x".to_string()));
+    }
+}
@@ -2,31 +2,44 @@ use std::{
     env,
     fs::{self, File},
     io::{self, IsTerminal, Read, Write},
+    os::unix::fs::FileExt,
     path::{Path, PathBuf},
     process::{self, ExitCode, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
     time::Duration,
 };
 
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use lazy_static::lazy_static;
 use mime::Mime;
 use quick_xml::{events::Event, Reader, Writer};
 use reqwest::{
     blocking,
-    header::{HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE},
-    Method,
+    header::{
+        HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE,
+        IF_NONE_MATCH, LAST_MODIFIED, RANGE,
+    },
+    Method, StatusCode, Url,
 };
 use termcolor::{BufferedStandardStream, Color, ColorChoice, ColorSpec, WriteColor};
 
 use crate::{
     body::Body,
+    cache::Cache,
+    checksum::Checksum,
     editor,
     error::Error,
     format::{self, format_request},
     highlight::highlight,
     http,
     image::Image,
-    progress::ProgressReader,
-    Cli,
+    progress::{HashingReader, ProgressReader},
+    retry::{send_with_retry, RetryPolicy},
+    Cli, ColorMode,
 };
 
 lazy_static! {
@@ -34,6 +47,25 @@ lazy_static! {
     pub(crate) static ref IS_STDERR_TTY: bool = std::io::stderr().is_terminal();
 }
 
+/// Whether the given color mode should resolve to enabled output for a
+/// stream whose TTY-ness is `is_tty`, honoring the `NO_COLOR` convention
+/// (see https://no-color.org) for the `auto` mode.
+fn is_color_enabled(mode: ColorMode, is_tty: bool) -> bool {
+    match mode {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => is_tty && env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+fn color_choice(enabled: bool) -> ColorChoice {
+    if enabled {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd)]
 pub(crate) enum Verbosity {
     Silent,
@@ -56,9 +88,10 @@ impl Verbosity {
 }
 
 pub(crate) fn fetch(opts: Cli) -> ExitCode {
+    let stderr_color = color_choice(is_color_enabled(opts.color, *IS_STDERR_TTY));
     match fetch_inner(opts) {
         Err(err) => {
-            let mut w = BufferedStandardStream::stderr(ColorChoice::Auto);
+            let mut w = BufferedStandardStream::stderr(stderr_color);
             _ = w.set_color(ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)));
             _ = w.write_all("Error".as_bytes());
             _ = w.reset();
@@ -76,12 +109,63 @@ pub(crate) fn fetch(opts: Cli) -> ExitCode {
 }
 
 fn fetch_inner(cli: Cli) -> Result<bool, Error> {
-    let mut req = create_request(&cli)?;
+    let (mut req, presigned_url) = create_request(&cli)?;
+    if let Some(url) = presigned_url {
+        println!("{url}");
+        return Ok(true);
+    }
+
+    let color_enabled = is_color_enabled(cli.color, *IS_STDOUT_TTY);
+    let stderr_color = color_choice(is_color_enabled(cli.color, *IS_STDERR_TTY));
+
+    // Only GET requests are cached; a cache is only constructed when
+    // `--cache` is passed, so the lookup/store calls below are cheap no-ops
+    // otherwise.
+    let cache = if cli.cache {
+        Some(Cache::new(duration_from_f64(cli.cache_ttl))?)
+    } else {
+        None
+    };
+    let cache_entry = cache
+        .as_ref()
+        .filter(|_| req.method() == Method::GET)
+        .and_then(|cache| cache.get(req.method(), req.url()));
+    if let Some(entry) = &cache_entry {
+        if let Some(etag) = &entry.etag {
+            req.headers_mut()
+                .insert(IF_NONE_MATCH, HeaderValue::from_str(etag).unwrap());
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            req.headers_mut().insert(
+                IF_MODIFIED_SINCE,
+                HeaderValue::from_str(last_modified).unwrap(),
+            );
+        }
+    }
+
+    // Resuming a download requires knowing how much of the output file
+    // already exists on disk, so that a `Range` request can be sent asking
+    // the server for only the remaining bytes.
+    let resume_from = if cli.continue_download {
+        cli.output
+            .as_deref()
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|meta| meta.len())
+            .filter(|&len| len > 0)
+    } else {
+        None
+    };
+    if let Some(pos) = resume_from {
+        req.headers_mut().insert(
+            RANGE,
+            HeaderValue::from_str(&format!("bytes={pos}-")).unwrap(),
+        );
+    }
 
     // Print request info if necessary.
     let v = Verbosity::new(&cli);
     if v > Verbosity::Verbose || cli.dry_run {
-        let mut stderr = BufferedStandardStream::stderr(ColorChoice::Auto);
+        let mut stderr = BufferedStandardStream::stderr(stderr_color);
         format_request(&mut stderr, &req)?;
         if cli.dry_run {
             if let Some(body) = req.body_mut() {
@@ -98,39 +182,134 @@ fn fetch_inner(cli: Cli) -> Result<bool, Error> {
         }
     }
 
-    let res = req.send()?;
+    let checksum = cli.checksum.as_deref().map(Checksum::parse).transpose()?;
+    let retry_policy = RetryPolicy::new(cli.retry, duration_from_f64(cli.retry_max_time));
+
+    // A segmented download replaces the single request/response below
+    // entirely, so attempt it first. `download_parallel` falls back to
+    // `None` (rather than erroring) when the server doesn't cooperate,
+    // e.g. it doesn't support range requests at all.
+    if let Some(segments) = cli.parallel.filter(|&n| n > 1) {
+        if let Some(output) = &cli.output {
+            if let Some(success) = download_parallel(
+                &req,
+                segments,
+                output,
+                matches!(v, Verbosity::Silent),
+                checksum.as_ref(),
+                &retry_policy,
+            )? {
+                return Ok(success);
+            }
+        }
+    }
+
+    let method = req.method().clone();
+    let url = req.url().clone();
+    let res = send_with_retry(req, &retry_policy, matches!(v, Verbosity::Silent))?;
     let version = res.version();
     let status = res.status();
     let is_success = (200..400).contains(&status.as_u16());
 
     if v > Verbosity::Silent {
-        let mut stderr = BufferedStandardStream::stderr(ColorChoice::Auto);
+        let mut stderr = BufferedStandardStream::stderr(stderr_color);
+        if !res.hops().is_empty() {
+            format::format_redirects(&mut stderr, res.hops())?;
+        }
         format::format_headers(&mut stderr, version, status, res.headers(), v)?;
     }
 
+    // A 304 means the cached entry is still fresh; serve it in place of the
+    // (bodyless) response.
+    if status == StatusCode::NOT_MODIFIED {
+        let entry = cache_entry
+            .ok_or_else(|| Error::new("received a 304 response without a cached entry"))?;
+        return display_cached(entry, &cli, color_enabled, v);
+    }
+
     // Write to a file if, specified.
     if let Some(output) = cli.output {
-        let mut file = fs::File::create(output)?;
-        let size = res.content_length();
+        // Only append if the server actually honored the range request with
+        // a matching `Content-Range` start; otherwise fall back to a full
+        // re-download.
+        let resumed = resume_from.is_some()
+            && status == StatusCode::PARTIAL_CONTENT
+            && content_range_start(res.headers()) == resume_from;
+        let start = if resumed { resume_from.unwrap() } else { 0 };
+        let mut file = if resumed {
+            fs::OpenOptions::new().append(true).open(&output)?
+        } else {
+            fs::File::create(&output)?
+        };
+        let size = res.content_length().map(|len| start + len);
         let reader = res.into_reader()?;
-        let mut reader = ProgressReader::new(reader, size, matches!(v, Verbosity::Silent));
-        io::copy(&mut reader, &mut file)?;
-        file.sync_all()?;
+        let reader = ProgressReader::with_start(reader, size, matches!(v, Verbosity::Silent), start);
+        // A resumed download only streams the newly-appended tail, so
+        // hashing that reader alone would checksum a fraction of the file.
+        // Hash as it streams when writing from scratch, but re-hash the
+        // whole file from disk afterwards when resuming, same as the
+        // segmented `--parallel` path.
+        if resumed {
+            let mut reader = reader;
+            io::copy(&mut reader, &mut file)?;
+            file.sync_all()?;
+            drop(file);
+            if let Some(checksum) = checksum {
+                let mut hashing = HashingReader::new(File::open(&output)?, checksum.hasher());
+                io::copy(&mut hashing, &mut io::sink())?;
+                if let Err(err) = checksum.verify(&hashing.finalize_hex()) {
+                    _ = fs::remove_file(&output);
+                    return Err(err);
+                }
+            }
+        } else if let Some(checksum) = checksum {
+            let mut reader = HashingReader::new(reader, checksum.hasher());
+            io::copy(&mut reader, &mut file)?;
+            file.sync_all()?;
+            if let Err(err) = checksum.verify(&reader.finalize_hex()) {
+                drop(file);
+                _ = fs::remove_file(&output);
+                return Err(err);
+            }
+        } else {
+            let mut reader = reader;
+            io::copy(&mut reader, &mut file)?;
+            file.sync_all()?;
+        }
         return Ok(is_success);
     }
 
     if *IS_STDOUT_TTY {
         // Stream response body to stdout.
         if let Some(content_type) = get_content_type(res.headers()) {
+            let etag = header_str(res.headers(), ETAG);
+            let last_modified = header_str(res.headers(), LAST_MODIFIED);
+            let content_type_str = header_str(res.headers(), CONTENT_TYPE);
+
             // TODO(ryanfowler): Limit body before reading it all.
             let mut buf = Vec::with_capacity(1024);
             res.into_reader()?.read_to_end(&mut buf)?;
+
+            if let Some(cache) = &cache {
+                if method == Method::GET && status == StatusCode::OK {
+                    _ = cache.store(
+                        &method,
+                        &url,
+                        content_type_str.as_deref(),
+                        etag.as_deref(),
+                        last_modified.as_deref(),
+                        &buf,
+                    );
+                }
+            }
+
             match content_type {
                 ContentType::Text(text_type) => {
+                    buf = decode_charset(buf, cli.charset.as_deref(), content_type_str.as_deref());
                     if let Some(formatted) = format_text(&buf, text_type) {
                         buf = formatted;
                     }
-                    if let Some(highlighted) = highlight(&buf, text_type) {
+                    if let Some(highlighted) = highlight(&buf, text_type, color_enabled) {
                         buf = highlighted;
                     }
                     stream_to_stdout(&mut &buf[..], cli.no_pager)?;
@@ -138,7 +317,7 @@ fn fetch_inner(cli: Cli) -> Result<bool, Error> {
                 }
                 ContentType::Image(_image) => {
                     if let Some(img) = Image::new(&buf) {
-                        img.write_to_stdout()?;
+                        img.write_to_stdout(cli.image_protocol)?;
                         Ok(is_success)
                     } else {
                         Err(Error::new("unable to parse image"))
@@ -152,6 +331,35 @@ fn fetch_inner(cli: Cli) -> Result<bool, Error> {
     } else {
         // stdout is not a tty, use a ProgressReader.
         let size = res.content_length();
+
+        // A `--cache` response still needs to be buffered in full so its
+        // body can be stored, even though this path otherwise streams
+        // straight through to stdout without buffering.
+        if let Some(cache) = &cache {
+            if method == Method::GET && status == StatusCode::OK {
+                let etag = header_str(res.headers(), ETAG);
+                let last_modified = header_str(res.headers(), LAST_MODIFIED);
+                let content_type_str = header_str(res.headers(), CONTENT_TYPE);
+
+                let mut buf = Vec::with_capacity(1024);
+                let reader = res.into_reader()?;
+                let mut reader = ProgressReader::new(reader, size, matches!(v, Verbosity::Silent));
+                reader.read_to_end(&mut buf)?;
+
+                _ = cache.store(
+                    &method,
+                    &url,
+                    content_type_str.as_deref(),
+                    etag.as_deref(),
+                    last_modified.as_deref(),
+                    &buf,
+                );
+
+                stream_to_stdout(&mut &buf[..], cli.no_pager)?;
+                return Ok(is_success);
+            }
+        }
+
         let reader = res.into_reader()?;
         let mut reader = ProgressReader::new(reader, size, matches!(v, Verbosity::Silent));
         stream_to_stdout(&mut reader, cli.no_pager)?;
@@ -159,14 +367,299 @@ fn fetch_inner(cli: Cli) -> Result<bool, Error> {
     }
 }
 
-fn create_request(cli: &Cli) -> Result<http::Request, Error> {
+// Transcodes a text body to UTF-8 ahead of the format/highlight pipeline,
+// using `--charset` when given, otherwise the `charset` parameter from the
+// response's `Content-Type` (defaulting to UTF-8 when neither is present).
+fn decode_charset(
+    buf: Vec<u8>,
+    charset_override: Option<&str>,
+    content_type: Option<&str>,
+) -> Vec<u8> {
+    let label = charset_override
+        .or_else(|| content_type.and_then(crate::charset::from_content_type))
+        .unwrap_or("utf-8");
+    crate::charset::decode(&buf, label)
+}
+
+// Parses the start offset out of a `Content-Range: bytes <start>-<end>/<total>`
+// response header, to confirm the server resumed from where we asked.
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+    let range = value.strip_prefix("bytes ")?;
+    let start = range.split(['-', '/']).next()?;
+    start.parse().ok()
+}
+
+// Parses the total resource size out of a `Content-Range: bytes
+// <start>-<end>/<total>` response header.
+fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+    let total = value.rsplit('/').next()?;
+    total.parse().ok()
+}
+
+// Attempts a segmented download of `req`'s resource, split across
+// `segments` concurrent range requests, each writing directly into its
+// region of `output` via positioned writes.
+//
+// Returns `Ok(None)` when the server doesn't support the preconditions
+// (a GET request, `206` on a probe range, and a known total size), so the
+// caller can fall back to the normal single-stream download instead.
+fn download_parallel(
+    req: &http::Request,
+    segments: u32,
+    output: &Path,
+    hidden: bool,
+    checksum: Option<&Checksum>,
+    retry_policy: &RetryPolicy,
+) -> Result<Option<bool>, Error> {
+    if *req.method() != Method::GET {
+        return Ok(None);
+    }
+
+    // Probe with a single-byte range request: a `206` response carrying a
+    // `Content-Range` total tells us the server supports ranges and the
+    // full resource size, without paying for the body twice.
+    let Some(mut probe) = req.try_clone() else {
+        return Ok(None);
+    };
+    probe
+        .headers_mut()
+        .insert(RANGE, HeaderValue::from_static("bytes=0-0"));
+    let probe_res = send_with_retry(probe, retry_policy, hidden)?;
+    if probe_res.status() != StatusCode::PARTIAL_CONTENT {
+        return Ok(None);
+    }
+    let Some(total) = content_range_total(probe_res.headers()) else {
+        return Ok(None);
+    };
+    if total == 0 {
+        return Ok(None);
+    }
+
+    // Split `[0, total)` into `segments` contiguous ranges, with the last
+    // segment absorbing any remainder.
+    let segments = (segments as u64).min(total).max(1);
+    let base = total / segments;
+    let remainder = total % segments;
+    let mut ranges = Vec::with_capacity(segments as usize);
+    let mut start = 0;
+    for i in 0..segments {
+        let len = base + if i == segments - 1 { remainder } else { 0 };
+        ranges.push((start, start + len - 1));
+        start += len;
+    }
+
+    // Clone one request per segment up front, on this thread: `Request`
+    // isn't `Sync`, so each worker needs to own its clone rather than
+    // share `req` across threads.
+    let segment_reqs = ranges
+        .iter()
+        .map(|_| req.try_clone())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::new("unable to clone request for segmented download"))?;
+
+    let file = File::create(output)?;
+    file.set_len(total)?;
+
+    let multi = MultiProgress::new();
+    if hidden {
+        multi.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    let aggregate = multi.add(
+        ProgressBar::new(total).with_style(
+            ProgressStyle::with_template(
+                "{bar:40.cyan/blue} {bytes}/{total_bytes:.bold} [{elapsed}]",
+            )
+            .unwrap(),
+        ),
+    );
+
+    let aborted = AtomicBool::new(false);
+    let first_error: Mutex<Option<Error>> = Mutex::new(None);
+
+    let segment_work = segment_reqs.into_iter().zip(ranges.iter()).enumerate();
+    thread::scope(|scope| {
+        for (i, (seg_req, &(start, end))) in segment_work {
+            let file = &file;
+            let multi = &multi;
+            let aggregate = &aggregate;
+            let aborted = &aborted;
+            let first_error = &first_error;
+            let retry_policy = &retry_policy;
+            scope.spawn(move || {
+                let segment_bar = multi.add(
+                    ProgressBar::new(end - start + 1).with_style(
+                        ProgressStyle::with_template(&format!(
+                            "  segment {}: {{bar:30.cyan/blue}} {{bytes}}/{{total_bytes}}",
+                            i + 1,
+                        ))
+                        .unwrap(),
+                    ),
+                );
+                let result = download_segment(
+                    seg_req,
+                    start,
+                    end,
+                    file,
+                    &segment_bar,
+                    aggregate,
+                    aborted,
+                    retry_policy,
+                    hidden,
+                );
+                segment_bar.finish_and_clear();
+                if let Err(err) = result {
+                    // Signal the other segments to stop writing, leaving a
+                    // clear partial-file state that `--continue` can later
+                    // resume from a single-stream download.
+                    aborted.store(true, Ordering::SeqCst);
+                    first_error.lock().unwrap().get_or_insert(err);
+                }
+            });
+        }
+    });
+
+    aggregate.finish();
+    file.sync_all()?;
+
+    if let Some(err) = first_error.into_inner().unwrap() {
+        return Err(err);
+    }
+
+    // Segments are written concurrently out of order, so there's no single
+    // stream to hash as bytes arrive; re-hash the assembled file instead.
+    if let Some(checksum) = checksum {
+        let mut reader = HashingReader::new(File::open(output)?, checksum.hasher());
+        io::copy(&mut reader, &mut io::sink())?;
+        if let Err(err) = checksum.verify(&reader.finalize_hex()) {
+            _ = fs::remove_file(output);
+            return Err(err);
+        }
+    }
+
+    Ok(Some(true))
+}
+
+// Downloads the `start..=end` byte range of `req`'s resource, writing each
+// chunk directly into `file` at its absolute offset so segments can
+// progress concurrently without sharing a cursor.
+fn download_segment(
+    mut req: http::Request,
+    start: u64,
+    end: u64,
+    file: &File,
+    segment_bar: &ProgressBar,
+    aggregate: &ProgressBar,
+    aborted: &AtomicBool,
+    retry_policy: &RetryPolicy,
+    hidden: bool,
+) -> Result<(), Error> {
+    req.headers_mut().insert(
+        RANGE,
+        HeaderValue::from_str(&format!("bytes={start}-{end}"))?,
+    );
+    let res = send_with_retry(req, retry_policy, hidden)?;
+    if res.status() != StatusCode::PARTIAL_CONTENT {
+        return Err(Error::new(format!(
+            "server returned {} for a segment's range request",
+            res.status()
+        )));
+    }
+
+    let mut reader = res.into_reader()?;
+    let mut buf = [0u8; 64 * 1024];
+    let mut offset = start;
+    while offset <= end {
+        if aborted.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let want = ((end - offset + 1) as usize).min(buf.len());
+        let n = reader.read(&mut buf[..want])?;
+        if n == 0 {
+            return Err(Error::new("connection closed before segment finished"));
+        }
+        file.write_at(&buf[..n], offset)?;
+        offset += n as u64;
+        segment_bar.inc(n as u64);
+        aggregate.inc(n as u64);
+    }
+    Ok(())
+}
+
+fn header_str(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+// Displays a cache entry in place of a live (304) response body.
+fn display_cached(
+    entry: crate::cache::Entry,
+    cli: &Cli,
+    color_enabled: bool,
+    v: Verbosity,
+) -> Result<bool, Error> {
+    if let Some(output) = &cli.output {
+        fs::write(output, &entry.body)?;
+        return Ok(true);
+    }
+
+    let mut buf = entry.body;
+    if *IS_STDOUT_TTY {
+        if let Some(content_type) = entry
+            .content_type
+            .as_deref()
+            .and_then(content_type_from_str)
+        {
+            match content_type {
+                ContentType::Text(text_type) => {
+                    buf =
+                        decode_charset(buf, cli.charset.as_deref(), entry.content_type.as_deref());
+                    if let Some(formatted) = format_text(&buf, text_type) {
+                        buf = formatted;
+                    }
+                    if let Some(highlighted) = highlight(&buf, text_type, color_enabled) {
+                        buf = highlighted;
+                    }
+                    stream_to_stdout(&mut &buf[..], cli.no_pager)?;
+                    return Ok(true);
+                }
+                ContentType::Image(_image) => {
+                    if let Some(img) = Image::new(&buf) {
+                        img.write_to_stdout(cli.image_protocol)?;
+                        return Ok(true);
+                    }
+                    return Err(Error::new("unable to parse image"));
+                }
+            }
+        }
+    }
+    _ = v;
+    stream_to_stdout(&mut &buf[..], cli.no_pager)?;
+    Ok(true)
+}
+
+fn create_request(cli: &Cli) -> Result<(http::Request, Option<Url>), Error> {
     let mut builder = http::RequestBuilder::new(&cli.url)
         .with_method(cli.method.as_deref())
         .with_headers(&cli.header)
         .with_basic(cli.basic.as_deref())
         .with_bearer(cli.bearer.as_deref())
+        .with_ca_cert(path_to_str(cli.ca_cert.as_deref()))
+        .with_client_cert(
+            path_to_str(cli.client_cert.as_deref()),
+            path_to_str(cli.client_key.as_deref()),
+        )
+        .with_cookie_jar(path_to_str(cli.cookie_jar.as_deref()))
+        .with_expect_continue(cli.expect_continue)
+        .with_insecure(cli.insecure)
+        .with_max_redirects(cli.max_redirects)
+        .with_no_decompress(cli.no_decompress)
         .with_proxy(cli.proxy.as_deref())
         .with_query(&cli.query)
+        .with_sigv4_streaming(cli.aws_sigv4_streaming)
         .with_timeout(duration_from_f64(cli.timeout))
         .with_version(cli.http);
 
@@ -176,6 +669,12 @@ fn create_request(cli: &Cli) -> Result<http::Request, Error> {
         sigv4 = Some(http::SigV4::parse(raw)?);
     }
 
+    // Parse out HTTP Message Signature parameters.
+    let mut signature: Option<http::HttpSignature> = None;
+    if let Some(raw) = &cli.signature {
+        signature = Some(http::HttpSignature::parse(raw)?);
+    }
+
     // Parse any request body. Only one of these can be defined, as per the
     // clap group they belong to.
     let content_type = get_cli_content_type(cli);
@@ -227,7 +726,7 @@ fn create_request(cli: &Cli) -> Result<http::Request, Error> {
     // Disallow sending a body with certain methods, as reqwest will
     // silently not send a body with these if the body is a type that
     // implements Read.
-    if (req.body_mut().is_some() || cli.edit)
+    if (req.has_body() || cli.edit)
         && matches!(req.method(), &Method::GET | &Method::HEAD | &Method::TRACE)
     {
         return Err(Error::new(format!(
@@ -252,14 +751,27 @@ fn create_request(cli: &Cli) -> Result<http::Request, Error> {
         *req.body_mut() = Some(body.into());
         req.headers_mut()
             .insert(CONTENT_LENGTH, HeaderValue::from_str(&length_str).unwrap());
+        req.clear_sigv4_streaming();
     }
 
-    // Sign the request, if necessary.
+    // Sign the request, if necessary. `--aws-sigv4-presign` builds a
+    // shareable URL instead of signing the request in place; in that case
+    // the request itself is never sent.
     if let Some(sigv4) = sigv4 {
+        if let Some(expires_secs) = cli.aws_sigv4_presign {
+            let url = req.presigned_url(&sigv4, expires_secs)?;
+            return Ok((req, Some(url)));
+        }
         req.sign(sigv4)?;
+    } else if let Some(signature) = signature {
+        req.sign_with_signature(signature)?;
     }
 
-    Ok(req)
+    Ok((req, None))
+}
+
+fn path_to_str(path: Option<&Path>) -> Option<&str> {
+    path.and_then(Path::to_str)
 }
 
 fn get_cli_content_type(cli: &Cli) -> Option<String> {
@@ -326,7 +838,11 @@ impl TextType {
 }
 
 fn get_content_type(headers: &HeaderMap) -> Option<ContentType> {
-    let mt: Mime = headers.get(CONTENT_TYPE)?.to_str().ok()?.parse().ok()?;
+    content_type_from_str(headers.get(CONTENT_TYPE)?.to_str().ok()?)
+}
+
+fn content_type_from_str(s: &str) -> Option<ContentType> {
+    let mt: Mime = s.parse().ok()?;
     match (mt.type_(), mt.subtype().as_str()) {
         (mime::IMAGE, "jpeg") => Some(ContentType::Image(ImageType::Jpeg)),
         (mime::IMAGE, "png") => Some(ContentType::Image(ImageType::Png)),
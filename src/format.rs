@@ -7,7 +7,21 @@ use reqwest::{
 };
 use termcolor::{Color, ColorSpec, WriteColor};
 
-use crate::fetch::Verbosity;
+use crate::{fetch::Verbosity, http::Hop};
+
+// Writes the chain of redirects that were followed before the final
+// response, one hop per line, e.g. `-> 301 https://example.com/new`.
+pub(crate) fn format_redirects(w: &mut impl WriteColor, hops: &[Hop]) -> io::Result<()> {
+    for hop in hops {
+        w.set_color(ColorSpec::new().set_dimmed(true))?;
+        write!(w, "-> ")?;
+        w.set_color(color_for_code(hop.status.as_u16()).set_bold(true))?;
+        write!(w, "{} ", hop.status.as_str())?;
+        w.set_color(ColorSpec::new().set_dimmed(true))?;
+        writeln!(w, "{}", hop.url)?;
+    }
+    w.reset()
+}
 
 pub(crate) fn format_headers(
     w: &mut impl WriteColor,
@@ -5,7 +5,11 @@ use tree_sitter_highlight::{HighlightConfiguration, HighlightEvent, Highlighter}
 
 use crate::{fetch::TextType, theme::Theme};
 
-pub(crate) fn highlight(input: &[u8], text_type: TextType) -> Option<Vec<u8>> {
+pub(crate) fn highlight(input: &[u8], text_type: TextType, color_enabled: bool) -> Option<Vec<u8>> {
+    if !color_enabled {
+        return None;
+    }
+
     let theme = Theme::default();
 
     let name = text_type.as_str();
@@ -1,26 +1,38 @@
 use std::{
-    env,
+    env, fs,
     io::{self, BufReader, Read},
     str::FromStr,
     time::Duration,
 };
 
-use flate2::bufread::{DeflateDecoder, GzDecoder};
+use flate2::bufread::{DeflateDecoder, MultiGzDecoder};
 use jiff::{tz::TimeZone, Zoned};
 use reqwest::{
     blocking::{self, Client, ClientBuilder},
     header::{
-        HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING,
-        CONTENT_LENGTH, USER_AGENT,
+        HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_ENCODING, AUTHORIZATION,
+        CONTENT_ENCODING, CONTENT_LENGTH, COOKIE, EXPECT, LOCATION, PROXY_AUTHORIZATION,
+        SET_COOKIE, USER_AGENT,
     },
-    Method, Proxy, StatusCode, Url, Version,
+    redirect, Certificate, Identity, Method, Proxy, StatusCode, Url, Version,
 };
 
-use crate::{aws_sigv4, body::Body, error::Error, Http};
+use crate::{aws_sigv4, body::Body, cookie::CookieJar, error::Error, http_signature, Http};
+
+pub(crate) use crate::http_signature::HttpSignature;
 
 static DEFAULT_CONNECT_TIMEOUT_MS: u64 = 60_000;
+static DEFAULT_MAX_REDIRECTS: u32 = 10;
 static APP_STRING: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// A single hop in a followed redirect chain: the status/Location that
+/// caused the hop, and the URL it resolved to.
+pub(crate) struct Hop {
+    pub(crate) status: StatusCode,
+    pub(crate) location: String,
+    pub(crate) url: Url,
+}
+
 #[derive(Copy, Clone, Debug)]
 enum ContentEncoding {
     None,
@@ -50,17 +62,32 @@ impl From<&str> for ContentEncoding {
     }
 }
 
+// Parses a `Content-Encoding` header value into the ordered list of codings
+// that were applied, e.g. "gzip, br" for a response that was gzipped and
+// then brotli-compressed on top.
+fn parse_content_encodings(value: &str) -> Vec<ContentEncoding> {
+    value.split(',').map(|v| v.trim().into()).collect()
+}
+
 pub(crate) struct RequestBuilder<'a> {
     url: &'a str,
     basic: Option<&'a str>,
     bearer: Option<&'a str>,
     body: Option<Body>,
+    ca_cert: Option<&'a str>,
+    client_cert: Option<(&'a str, &'a str)>,
     content_type: Option<&'a str>,
+    cookie_jar: Option<&'a str>,
+    expect_continue: bool,
+    insecure: bool,
+    max_redirects: u32,
     method: Option<&'a str>,
     multipart: Option<blocking::multipart::Form>,
     headers: &'a [String],
+    no_decompress: bool,
     proxy: Option<&'a str>,
     query: &'a [String],
+    sigv4_streaming: bool,
     timeout: Option<Duration>,
     version: Option<Http>,
 }
@@ -72,12 +99,20 @@ impl<'a> RequestBuilder<'a> {
             basic: None,
             bearer: None,
             body: None,
+            ca_cert: None,
+            client_cert: None,
             content_type: None,
+            cookie_jar: None,
+            expect_continue: false,
+            insecure: false,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
             method: None,
             multipart: None,
             headers: &[],
+            no_decompress: false,
             proxy: None,
             query: &[],
+            sigv4_streaming: false,
             timeout: None,
             version: None,
         }
@@ -113,6 +148,44 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    pub(crate) fn with_cookie_jar(mut self, cookie_jar: Option<&'a str>) -> Self {
+        self.cookie_jar = cookie_jar;
+        self
+    }
+
+    pub(crate) fn with_ca_cert(mut self, ca_cert: Option<&'a str>) -> Self {
+        self.ca_cert = ca_cert;
+        self
+    }
+
+    pub(crate) fn with_expect_continue(mut self, expect_continue: bool) -> Self {
+        self.expect_continue = expect_continue;
+        self
+    }
+
+    // When set, a file body is kept back (rather than attached to the
+    // request immediately) so that `Request::sign()` can re-frame it as a
+    // chunked, streaming-signed `aws-chunked` upload.
+    pub(crate) fn with_sigv4_streaming(mut self, streaming: bool) -> Self {
+        self.sigv4_streaming = streaming;
+        self
+    }
+
+    pub(crate) fn with_client_cert(mut self, cert: Option<&'a str>, key: Option<&'a str>) -> Self {
+        self.client_cert = cert.zip(key);
+        self
+    }
+
+    pub(crate) fn with_insecure(mut self, insecure: bool) -> Self {
+        self.insecure = insecure;
+        self
+    }
+
+    pub(crate) fn with_max_redirects(mut self, max_redirects: u32) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
     pub(crate) fn with_body(mut self, body: Option<Body>) -> Self {
         self.body = body;
         self
@@ -138,6 +211,14 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    // When set, the response body is handed to consumers exactly as the
+    // server sent it, skipping the `Content-Encoding` decode step in
+    // `Request::send()` even though `Accept-Encoding` is still advertised.
+    pub(crate) fn with_no_decompress(mut self, no_decompress: bool) -> Self {
+        self.no_decompress = no_decompress;
+        self
+    }
+
     pub(crate) fn build(self) -> Result<Request, Error> {
         // Parse our request dependencies.
         let url = parse_url(self.url)?;
@@ -146,20 +227,38 @@ impl<'a> RequestBuilder<'a> {
         let query = parse_query(self.query);
 
         // Build the blocking HTTP client.
+        // Redirects are followed manually in `Request::send()` so that
+        // sensitive headers can be stripped on cross-origin hops and the
+        // full chain can be recorded; reqwest must never follow on its own.
         let mut builder = ClientBuilder::new()
             .use_rustls_tls()
+            .redirect(redirect::Policy::none())
             .timeout(self.timeout)
             .connect_timeout(Duration::from_millis(DEFAULT_CONNECT_TIMEOUT_MS));
         if let Some(v) = self.version {
             builder = match v {
                 Http::One => builder.http1_only(),
                 Http::Two => builder.http2_prior_knowledge(),
-                // Http::Three => builder.http3_prior_knowledge(),
+                Http::Three => builder.http3_prior_knowledge(),
             }
         }
         if let Some(proxy) = self.proxy {
             builder = builder.proxy(Proxy::all(proxy)?);
         }
+        if self.insecure {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        if let Some(path) = self.ca_cert {
+            let pem = fs::read(path)?;
+            for block in split_pem_certificates(&pem) {
+                builder = builder.add_root_certificate(Certificate::from_pem(&block)?);
+            }
+        }
+        if let Some((cert_path, key_path)) = self.client_cert {
+            let mut pem = fs::read(cert_path)?;
+            pem.extend(fs::read(key_path)?);
+            builder = builder.identity(Identity::from_pem(&pem)?);
+        }
         let client = builder.build()?;
 
         // Build the blocking HTTP request.
@@ -217,10 +316,11 @@ impl<'a> RequestBuilder<'a> {
             *req.version_mut() = match version {
                 Http::One => Version::HTTP_11,
                 Http::Two => Version::HTTP_2,
-                // Http::Three => Version::HTTP_3,
+                Http::Three => Version::HTTP_3,
             };
         }
 
+        let mut streaming_body = None;
         if let Some(body) = self.body {
             if let Some(content_length) = body.content_length() {
                 req.headers_mut().insert(
@@ -228,13 +328,46 @@ impl<'a> RequestBuilder<'a> {
                     HeaderValue::from_str(&content_length.to_string()).unwrap(),
                 );
             }
-            *req.body_mut() = Some(body.into());
+            // Setting this header is all that's required: the HTTP/1.1
+            // transport holds the body back and waits for the interim `100
+            // Continue` (or aborts early on a final 4xx/5xx) before writing
+            // it, the same way redirect/version handling below is left to
+            // the underlying client rather than reimplemented here.
+            if self.expect_continue {
+                req.headers_mut()
+                    .insert(EXPECT, HeaderValue::from_static("100-continue"));
+            }
+            match body {
+                // Hold the file back rather than attaching it now: it'll be
+                // re-framed as a chunked, streaming-signed body in `sign()`,
+                // once the seed signature is known.
+                Body::File((file, Some(size))) if self.sigv4_streaming => {
+                    streaming_body = Some((file, size));
+                }
+                body => {
+                    *req.body_mut() = Some(body.into());
+                }
+            }
+        }
+
+        // Load the cookie jar, if configured, and attach any cookies that
+        // apply to this request's URL.
+        let cookie_jar = self.cookie_jar.map(CookieJar::load).transpose()?;
+        if let Some(jar) = &cookie_jar {
+            if let Some(value) = jar.header_for(req.url()) {
+                req.headers_mut()
+                    .insert(COOKIE, HeaderValue::from_str(&value)?);
+            }
         }
 
         Ok(Request {
             client,
             req,
             encoding_requested,
+            no_decompress: self.no_decompress,
+            cookie_jar,
+            max_redirects: self.max_redirects,
+            streaming_body,
         })
     }
 }
@@ -243,10 +376,14 @@ pub(crate) struct Request {
     client: Client,
     req: blocking::Request,
     encoding_requested: bool,
+    no_decompress: bool,
+    cookie_jar: Option<CookieJar>,
+    max_redirects: u32,
+    streaming_body: Option<(fs::File, u64)>,
 }
 
 impl Request {
-    #[allow(dead_code)] // Used in aws-sigv4 testing.
+    #[allow(dead_code)] // Used in aws-sigv4 and http-signature testing.
     pub(crate) fn new_test(method: Method, url: Url) -> Self {
         let client = Client::new();
         let req = blocking::Request::new(method, url);
@@ -254,22 +391,120 @@ impl Request {
             client,
             req,
             encoding_requested: false,
+            no_decompress: false,
+            cookie_jar: None,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            streaming_body: None,
         }
     }
 
-    pub(crate) fn send(self) -> Result<Response, Error> {
-        let res = self.client.execute(self.req)?;
+    pub(crate) fn send(mut self) -> Result<Response, Error> {
+        let mut hops = Vec::new();
+        let mut remaining = self.max_redirects;
+        let mut req = self.req;
+
+        let res = loop {
+            let from_url = req.url().clone();
+            let next = req.try_clone();
+            let res = self.client.execute(req)?;
+
+            if let Some(jar) = &mut self.cookie_jar {
+                let values = res
+                    .headers()
+                    .get_all(SET_COOKIE)
+                    .iter()
+                    .filter_map(|v| v.to_str().ok());
+                jar.merge_set_cookie(&from_url, values);
+                jar.save()?;
+            }
+
+            if !res.status().is_redirection() || remaining == 0 {
+                break res;
+            }
+            let Some(location) = res
+                .headers()
+                .get(LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+            else {
+                break res;
+            };
+            let Some(mut next) = next else {
+                return Err(Error::new(
+                    "cannot follow redirect: request body cannot be re-sent",
+                ));
+            };
+            let next_url = from_url
+                .join(&location)
+                .map_err(|_| Error::new(format!("redirect: invalid location '{location}'")))?;
+
+            // 301/302/303 redirects conventionally downgrade the method to
+            // GET and drop the body; 307/308 preserve both.
+            if matches!(
+                res.status(),
+                StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND | StatusCode::SEE_OTHER
+            ) {
+                *next.method_mut() = Method::GET;
+                *next.body_mut() = None;
+                next.headers_mut().remove(CONTENT_LENGTH);
+            }
+            *next.url_mut() = next_url.clone();
+
+            if !same_origin(&from_url, &next_url) {
+                for header in [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE] {
+                    next.headers_mut().remove(header);
+                }
+                next.headers_mut().remove("signature");
+                next.headers_mut().remove("signature-input");
+                // Every `x-amz-*` header is specific to the original
+                // (same-origin) SigV4 request, and `x-amz-security-token`
+                // in particular carries a live STS session credential —
+                // none of it should follow a redirect off-host.
+                let amz_headers: Vec<HeaderName> = next
+                    .headers()
+                    .keys()
+                    .filter(|name| name.as_str().starts_with("x-amz-"))
+                    .cloned()
+                    .collect();
+                for header in amz_headers {
+                    next.headers_mut().remove(header);
+                }
+            }
+            if let Some(jar) = &self.cookie_jar {
+                match jar.header_for(&next_url) {
+                    Some(value) => {
+                        next.headers_mut()
+                            .insert(COOKIE, HeaderValue::from_str(&value)?);
+                    }
+                    None => {
+                        next.headers_mut().remove(COOKIE);
+                    }
+                }
+            }
+
+            hops.push(Hop {
+                status: res.status(),
+                location,
+                url: next_url,
+            });
+            remaining -= 1;
+            req = next;
+        };
 
-        let mut enc = ContentEncoding::None;
-        if self.encoding_requested {
+        let mut encodings = Vec::new();
+        if self.encoding_requested && !self.no_decompress {
             if let Some(encoding) = res.headers().get(CONTENT_ENCODING) {
                 if let Ok(encoding) = encoding.to_str() {
-                    enc = ContentEncoding::from(encoding);
+                    encodings = parse_content_encodings(encoding);
                 }
             }
         }
 
-        Ok(Response { res, enc })
+        Ok(Response {
+            res,
+            encodings,
+            hops,
+        })
     }
 
     pub(crate) fn version(&self) -> Version {
@@ -296,22 +531,79 @@ impl Request {
         self.req.body_mut()
     }
 
+    // True if a body is attached, whether as a normal request body or one
+    // held back for streaming SigV4 signing.
+    pub(crate) fn has_body(&self) -> bool {
+        self.req.body().is_some() || self.streaming_body.is_some()
+    }
+
+    // Clones this request so it can be re-sent independently, e.g. as one
+    // of several concurrent range requests in a segmented download. Fails
+    // only if the body is a non-rewindable stream, which never applies to
+    // the bodyless GET/HEAD probes this is used for.
+    pub(crate) fn try_clone(&self) -> Option<Request> {
+        Some(Request {
+            client: self.client.clone(),
+            req: self.req.try_clone()?,
+            encoding_requested: self.encoding_requested,
+            no_decompress: self.no_decompress,
+            cookie_jar: self.cookie_jar.clone(),
+            max_redirects: self.max_redirects,
+            streaming_body: None,
+        })
+    }
+
     pub(crate) fn sign(&mut self, sigv4: SigV4) -> Result<(), Error> {
         let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let streaming_body = self.streaming_body.take();
         aws_sigv4::sign(
             self,
             &sigv4.access_key,
             &sigv4.secret_key,
+            sigv4.session_token.as_deref(),
+            &sigv4.region,
+            &sigv4.service,
+            &now,
+            streaming_body,
+        )
+    }
+
+    // Drops any file body held back for streaming SigV4 signing, falling
+    // back to a normal in-memory signature if the body ends up replaced
+    // (e.g. by `--edit`) before `sign()` is called.
+    pub(crate) fn clear_sigv4_streaming(&mut self) {
+        self.streaming_body = None;
+    }
+
+    // Builds a presigned URL rather than signing the request in place; the
+    // request itself is never sent.
+    pub(crate) fn presigned_url(&self, sigv4: &SigV4, expires_secs: u64) -> Result<Url, Error> {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        aws_sigv4::presign(
+            self,
+            &sigv4.access_key,
+            &sigv4.secret_key,
+            sigv4.session_token.as_deref(),
             &sigv4.region,
             &sigv4.service,
+            expires_secs,
             &now,
         )
     }
+
+    pub(crate) fn sign_with_signature(
+        &mut self,
+        sig: http_signature::HttpSignature,
+    ) -> Result<(), Error> {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        http_signature::sign(self, &sig, &now)
+    }
 }
 
 pub(crate) struct Response {
     res: blocking::Response,
-    enc: ContentEncoding,
+    encodings: Vec<ContentEncoding>,
+    hops: Vec<Hop>,
 }
 
 impl Response {
@@ -319,6 +611,10 @@ impl Response {
         self.res.status()
     }
 
+    pub(crate) fn hops(&self) -> &[Hop] {
+        &self.hops
+    }
+
     pub(crate) fn version(&self) -> Version {
         self.res.version()
     }
@@ -332,8 +628,42 @@ impl Response {
     }
 
     pub(crate) fn into_reader(self) -> io::Result<impl Read> {
-        Decoder::new(self.res, self.enc)
+        build_decoder(self.res, &self.encodings)
+    }
+}
+
+// Splits a PEM file into its individual `CERTIFICATE` blocks, so a bundle
+// containing multiple roots (e.g. a full chain) can be added one at a time
+// via `add_root_certificate`.
+fn split_pem_certificates(data: &[u8]) -> Vec<Vec<u8>> {
+    let text = String::from_utf8_lossy(data);
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut in_cert = false;
+    for line in text.lines() {
+        if line.starts_with("-----BEGIN CERTIFICATE-----") {
+            in_cert = true;
+            current.clear();
+        }
+        if in_cert {
+            current.push_str(line);
+            current.push('\n');
+        }
+        if line.starts_with("-----END CERTIFICATE-----") {
+            in_cert = false;
+            out.push(std::mem::take(&mut current).into_bytes());
+        }
     }
+    out
+}
+
+// Two URLs are same-origin if they share a scheme, host, and (explicit or
+// default) port; used to decide whether to strip sensitive headers across
+// a redirect hop.
+fn same_origin(a: &Url, b: &Url) -> bool {
+    a.scheme() == b.scheme()
+        && a.host_str() == b.host_str()
+        && a.port_or_known_default() == b.port_or_known_default()
 }
 
 fn parse_url(url: &str) -> Result<Url, Error> {
@@ -398,6 +728,7 @@ pub(crate) struct SigV4 {
     service: String,
     access_key: String,
     secret_key: String,
+    session_token: Option<String>,
 }
 
 impl SigV4 {
@@ -408,12 +739,14 @@ impl SigV4 {
         };
         let access_key = get_sigv4_var("AWS_ACCESS_KEY_ID")?;
         let secret_key = get_sigv4_var("AWS_SECRET_ACCESS_KEY")?;
+        let session_token = env::var("AWS_SESSION_TOKEN").ok();
 
         Ok(Self {
             region: region.to_string(),
             service: service.to_string(),
             access_key,
             secret_key,
+            session_token,
         })
     }
 }
@@ -422,44 +755,35 @@ fn get_sigv4_var(key: &str) -> Result<String, Error> {
     env::var(key).map_err(|_| Error::new(format!("aws-sigv4: {key} env var must be set")))
 }
 
-enum Decoder<'a, R: Read> {
-    Passthrough(R),
-    Brotli(Box<brotli::Decompressor<R>>),
-    Deflate(Box<DeflateDecoder<BufReader<R>>>),
-    Gzip(Box<GzDecoder<BufReader<R>>>),
-    Zstd(Box<zstd::Decoder<'a, BufReader<R>>>),
-}
-
-impl<R: Read> Decoder<'_, R> {
-    fn new(r: R, ct: ContentEncoding) -> io::Result<Self> {
-        Ok(match ct {
-            ContentEncoding::None => Self::Passthrough(r),
-            ContentEncoding::Gzip => Self::Gzip(Box::new(GzDecoder::new(
-                BufReader::with_capacity(1 << 14, r),
+// Builds a reader that unwraps a response body through every coding listed
+// in a `Content-Encoding` header. Codings are applied to the body in the
+// header's order, so they must be undone in reverse, outermost first.
+// `MultiGzDecoder` (rather than `GzDecoder`) is used for the gzip case since
+// some servers concatenate multiple gzip members into a single body, which a
+// single-member decoder would silently truncate after the first one.
+fn build_decoder<'a>(
+    r: impl Read + 'a,
+    encodings: &[ContentEncoding],
+) -> io::Result<Box<dyn Read + 'a>> {
+    let mut reader: Box<dyn Read + 'a> = Box::new(r);
+    for ct in encodings.iter().rev() {
+        reader = match ct {
+            ContentEncoding::None => reader,
+            ContentEncoding::Gzip => Box::new(MultiGzDecoder::new(BufReader::with_capacity(
+                1 << 14,
+                reader,
             ))),
-            ContentEncoding::Deflate => Self::Deflate(Box::new(DeflateDecoder::new(
-                BufReader::with_capacity(1 << 14, r),
+            ContentEncoding::Deflate => Box::new(DeflateDecoder::new(BufReader::with_capacity(
+                1 << 14,
+                reader,
             ))),
-            ContentEncoding::Brotli => {
-                Self::Brotli(Box::new(brotli::Decompressor::new(r, 1 << 14)))
-            }
-            ContentEncoding::Zstd => Self::Zstd(Box::new(zstd::Decoder::with_buffer(
-                BufReader::with_capacity(1 << 14, r),
-            )?)),
-        })
-    }
-}
-
-impl<R: Read> Read for Decoder<'_, R> {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        match self {
-            Decoder::Passthrough(r) => r.read(buf),
-            Decoder::Brotli(r) => r.read(buf),
-            Decoder::Deflate(r) => r.read(buf),
-            Decoder::Gzip(r) => r.read(buf),
-            Decoder::Zstd(r) => r.read(buf),
-        }
+            ContentEncoding::Brotli => Box::new(brotli::Decompressor::new(reader, 1 << 14)),
+            ContentEncoding::Zstd => Box::new(zstd::Decoder::with_buffer(
+                BufReader::with_capacity(1 << 14, reader),
+            )?),
+        };
     }
+    Ok(reader)
 }
 
 #[cfg(test)]
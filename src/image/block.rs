@@ -87,7 +87,14 @@ fn write_blocks(stdout: &mut impl WriteColor, img: &DynamicImage) -> io::Result<
 }
 
 fn supports_truecolor() -> bool {
-    env::var("COLORTERM").is_ok_and(|v| v.contains("truecolor") || v.contains("24bit"))
+    // Fast path: most truecolor-capable terminals export COLORTERM.
+    if env::var("COLORTERM").is_ok_and(|v| v.contains("truecolor") || v.contains("24bit")) {
+        return true;
+    }
+    // Fall back to probing the terminfo entry for `$TERM`, which catches
+    // terminals that advertise truecolor there instead (e.g. under sudo,
+    // over SSH, or inside some multiplexers).
+    crate::terminfo::supports_truecolor()
 }
 
 fn get_color_from_pixel(pixel: (u32, u32, &Rgba<u8>), truecolor: bool) -> Color {
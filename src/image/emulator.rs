@@ -1,4 +1,11 @@
-use std::env;
+use std::{
+    env,
+    io::{self, IsTerminal, Read, Write},
+    sync::mpsc,
+    time::Duration,
+};
+
+use crossterm::terminal;
 
 use super::Protocol;
 
@@ -7,16 +14,20 @@ pub(crate) enum Emulator {
     Alacritty,
     Apple,
     Ghostty,
+    Foot,
     Hyper,
     Iterm2,
     Kitty,
     Konsole,
     Mintty,
+    MlTerm,
+    Sixel,
     Tmux,
     Unknown,
     VSCode,
     WezTerm,
     Windows,
+    XtermSixel,
     Zellij,
 }
 
@@ -38,6 +49,14 @@ impl Emulator {
             return emulator;
         }
 
+        // No environment variable gave us an answer (e.g. xterm, mlterm,
+        // foot and WezTerm builds configured without their identifying
+        // variables). Ask the terminal itself via a DA1 query before
+        // settling for the coarse block fallback.
+        if probe_sixel_support() {
+            return Self::Sixel;
+        }
+
         Self::Unknown
     }
 
@@ -69,6 +88,8 @@ impl Emulator {
             ("alacritty", Self::Alacritty),
             ("xterm-ghostty", Self::Ghostty),
             ("xterm-kitty", Self::Kitty),
+            ("foot", Self::Foot),
+            ("mlterm", Self::MlTerm),
         ];
 
         if let Ok(var) = env::var("TERM") {
@@ -77,6 +98,11 @@ impl Emulator {
                     return Some(emulator);
                 }
             }
+            // Plain xterm (and its many derivatives) generally support
+            // sixel graphics when compiled/configured with `--enable-sixel`.
+            if var.starts_with("xterm") {
+                return Some(Self::XtermSixel);
+            }
         }
         None
     }
@@ -104,18 +130,93 @@ impl Emulator {
         match self {
             Emulator::Alacritty => Protocol::Block,
             Emulator::Apple => Protocol::Block,
+            Emulator::Foot => Protocol::Sixel,
             Emulator::Ghostty => Protocol::Kitty,
             Emulator::Hyper => Protocol::InlineImages,
             Emulator::Iterm2 => Protocol::InlineImages,
             Emulator::Kitty => Protocol::Kitty,
             Emulator::Konsole => Protocol::Kitty,
             Emulator::Mintty => Protocol::InlineImages,
+            Emulator::MlTerm => Protocol::Sixel,
+            Emulator::Sixel => Protocol::Sixel,
             Emulator::Tmux => Protocol::Block,
             Emulator::Unknown => Protocol::Block,
             Emulator::VSCode => Protocol::Block,
             Emulator::WezTerm => Protocol::InlineImages,
             Emulator::Windows => Protocol::Block,
+            Emulator::XtermSixel => Protocol::Sixel,
             Emulator::Zellij => Protocol::Block,
         }
     }
 }
+
+// Queries the terminal's primary device attributes (DA1) and checks
+// whether the response advertises sixel graphics, capability `4`:
+// https://vt100.net/docs/vt510-rm/DA1.html
+//
+// This only runs once none of the environment-variable heuristics above
+// matched, so it's the fallback of last resort for terminals (or SSH
+// sessions, or multiplexers) that don't identify themselves any other
+// way but do support sixel.
+fn probe_sixel_support() -> bool {
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return false;
+    }
+    if terminal::enable_raw_mode().is_err() {
+        return false;
+    }
+    let response = query_da1();
+    let _ = terminal::disable_raw_mode();
+
+    response.is_some_and(|resp| has_capability(&resp, '4'))
+}
+
+fn query_da1() -> Option<String> {
+    write!(io::stdout(), "\x1b[c").ok()?;
+    io::stdout().flush().ok()?;
+
+    // Read the reply on a background thread so an unresponsive terminal
+    // can't hang the probe; give up after a short timeout.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut bytes = Vec::new();
+        let mut byte = [0u8];
+        while bytes.len() < 64 {
+            match stdin.read(&mut byte) {
+                Ok(1) => {
+                    bytes.push(byte[0]);
+                    if byte[0] == b'c' {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+        let _ = tx.send(bytes);
+    });
+
+    let bytes = rx.recv_timeout(Duration::from_millis(200)).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+// A DA1 response looks like `ESC [ ? Pm c`, where `Pm` is a
+// semicolon-separated list of supported capabilities.
+fn has_capability(response: &str, capability: char) -> bool {
+    response
+        .strip_prefix("\x1b[?")
+        .and_then(|s| s.strip_suffix('c'))
+        .is_some_and(|params| params.split(';').any(|p| p.len() == 1 && p.starts_with(capability)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_capability() {
+        assert!(has_capability("\x1b[?64;1;2;4;6;9;15c", '4'));
+        assert!(!has_capability("\x1b[?64;1;2;6;9;15c", '4'));
+        assert!(!has_capability("garbage", '4'));
+    }
+}
@@ -7,16 +7,20 @@ use image::{load_from_memory_with_format, DynamicImage, GenericImageView, ImageF
 
 use emulator::Emulator;
 
+use crate::ImageProtocol;
+
 mod block;
 mod emulator;
 mod inline;
 mod kitty;
+mod sixel;
 
 #[derive(Copy, Clone, Debug)]
 enum Protocol {
     Block,
     InlineImages,
     Kitty,
+    Sixel,
 }
 
 pub(crate) struct Image {
@@ -32,18 +36,28 @@ impl Image {
         &self.img
     }
 
-    pub(crate) fn write_to_stdout(self) -> std::io::Result<()> {
+    pub(crate) fn write_to_stdout(self, protocol: ImageProtocol) -> std::io::Result<()> {
         // If any of the image's dimensions are zero, return immediately.
         let (width, height) = self.img.dimensions();
         if width == 0 || height == 0 {
             return Ok(());
         }
 
-        let emulator = Emulator::detect();
-        match emulator.supported_protocol() {
+        let protocol = match protocol {
+            ImageProtocol::Auto => Emulator::detect().supported_protocol(),
+            ImageProtocol::Kitty => Protocol::Kitty,
+            ImageProtocol::Iterm2 => Protocol::InlineImages,
+            ImageProtocol::Sixel => Protocol::Sixel,
+            ImageProtocol::None => {
+                println!("image: {width}x{height}");
+                return Ok(());
+            }
+        };
+        match protocol {
             Protocol::Block => block::write_to_stdout(self),
             Protocol::InlineImages => inline::write_to_stdout(self),
             Protocol::Kitty => kitty::write_to_stdout(self),
+            Protocol::Sixel => sixel::write_to_stdout(self),
         }
     }
 
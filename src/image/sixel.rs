@@ -0,0 +1,137 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use image::{DynamicImage, GenericImageView};
+
+use super::Image;
+
+static ESC: &str = "\x1b";
+
+pub(crate) fn write_to_stdout(img: Image) -> io::Result<()> {
+    let img = img.resize_for_term();
+    let img = img.dynamic_image();
+    let (width, height) = img.dimensions();
+
+    let (palette, indices) = quantize(img);
+
+    let mut stdout = io::BufWriter::with_capacity(1 << 16, io::stdout());
+    write!(&mut stdout, "{ESC}Pq")?;
+
+    for (i, &(r, g, b)) in palette.iter().enumerate() {
+        // Sixel color registers are expressed as percentages (0-100), not bytes.
+        write!(
+            &mut stdout,
+            "#{};2;{};{};{}",
+            i,
+            r as u32 * 100 / 255,
+            g as u32 * 100 / 255,
+            b as u32 * 100 / 255,
+        )?;
+    }
+
+    let mut row = 0;
+    while row < height {
+        let band_height = (height - row).min(6);
+        write_band(&mut stdout, &indices, palette.len(), width, row, band_height)?;
+        row += 6;
+        if row < height {
+            write!(&mut stdout, "-")?;
+        }
+    }
+
+    write!(&mut stdout, "{ESC}\\")?;
+    writeln!(&mut stdout)?;
+    stdout.flush()
+}
+
+/// Writes a single six-pixel-tall band of the image as one or more sixel
+/// "layers", one per palette color, returning to the start of the band
+/// (`$`) between layers.
+fn write_band(
+    w: &mut impl Write,
+    indices: &[Option<u8>],
+    palette_len: usize,
+    width: u32,
+    start_row: u32,
+    band_height: u32,
+) -> io::Result<()> {
+    for color in 0..palette_len as u8 {
+        let mut used = false;
+        let mut codes = Vec::with_capacity(width as usize);
+        for col in 0..width {
+            let mut mask = 0u8;
+            for r in 0..band_height {
+                let idx = ((start_row + r) * width + col) as usize;
+                if indices[idx] == Some(color) {
+                    mask |= 1 << r;
+                    used = true;
+                }
+            }
+            codes.push(0x3F + mask);
+        }
+        if !used {
+            continue;
+        }
+        write!(w, "#{color}")?;
+        write_rle(w, &codes)?;
+        write!(w, "$")?;
+    }
+    Ok(())
+}
+
+/// Writes a row of sixel character codes, using the `!<count><char>`
+/// repeat-count escape for runs of four or more identical characters.
+fn write_rle(w: &mut impl Write, codes: &[u8]) -> io::Result<()> {
+    let mut i = 0;
+    while i < codes.len() {
+        let ch = codes[i];
+        let mut j = i + 1;
+        while j < codes.len() && codes[j] == ch {
+            j += 1;
+        }
+        let run = j - i;
+        if run >= 4 {
+            write!(w, "!{run}{}", ch as char)?;
+        } else {
+            for _ in 0..run {
+                w.write_all(&[ch])?;
+            }
+        }
+        i = j;
+    }
+    Ok(())
+}
+
+/// Quantizes the image to a palette of at most 216 colors using a uniform
+/// RGB cube (6 levels per channel), returning the palette and a per-pixel
+/// palette index. Fully transparent pixels map to `None` so they're left
+/// untouched, matching how the half-block renderer treats alpha.
+fn quantize(img: &DynamicImage) -> (Vec<(u8, u8, u8)>, Vec<Option<u8>>) {
+    const LEVELS: u32 = 6;
+
+    let rgba = img.to_rgba8();
+    let mut palette = Vec::new();
+    let mut lookup = HashMap::new();
+    let mut indices = Vec::with_capacity((rgba.width() * rgba.height()) as usize);
+
+    let bucket = |c: u8| (c as u32 * (LEVELS - 1) / 255) as u8;
+    let unbucket = |l: u8| (l as u32 * 255 / (LEVELS - 1)) as u8;
+
+    for pixel in rgba.pixels() {
+        if pixel[3] == 0 {
+            indices.push(None);
+            continue;
+        }
+        let key = (bucket(pixel[0]), bucket(pixel[1]), bucket(pixel[2]));
+        let index = *lookup.entry(key).or_insert_with(|| {
+            let idx = palette.len() as u8;
+            palette.push((unbucket(key.0), unbucket(key.1), unbucket(key.2)));
+            idx
+        });
+        indices.push(Some(index));
+    }
+
+    (palette, indices)
+}
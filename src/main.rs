@@ -4,14 +4,21 @@ use clap::{ArgAction, Parser, ValueEnum};
 
 mod aws_sigv4;
 mod body;
+mod cache;
+mod charset;
+mod checksum;
+mod cookie;
 mod editor;
 mod error;
 mod fetch;
 mod format;
 mod highlight;
 mod http;
+mod http_signature;
 mod image;
 mod progress;
+mod retry;
+mod terminfo;
 mod theme;
 mod update;
 
@@ -35,6 +42,12 @@ struct Cli {
     /// Sign the request using AWS signature V4
     #[arg(long, value_name = "REGION/SERVICE")]
     aws_sigv4: Option<String>,
+    /// Print a presigned AWS SigV4 URL instead of sending the request
+    #[arg(long, value_name = "SECONDS", requires = "aws_sigv4")]
+    aws_sigv4_presign: Option<u64>,
+    /// Sign a file request body as a chunked streaming upload, without buffering it
+    #[arg(long, requires = "aws_sigv4", conflicts_with = "aws_sigv4_presign")]
+    aws_sigv4_streaming: bool,
     /// Enable HTTP basic authentication
     #[arg(long, value_name = "USER:PASS")]
     #[arg(conflicts_with = "aws_sigv4", conflicts_with = "bearer")]
@@ -43,6 +56,36 @@ struct Cli {
     #[arg(long, value_name = "TOKEN")]
     #[arg(conflicts_with = "aws_sigv4", conflicts_with = "basic")]
     bearer: Option<String>,
+    /// Trust an additional CA certificate bundle (PEM, may contain multiple roots)
+    #[arg(long, value_name = "FILE")]
+    ca_cert: Option<PathBuf>,
+    /// Cache GET responses on disk and revalidate with conditional requests
+    #[arg(long)]
+    cache: bool,
+    /// Override the on-disk cache entry TTL, in seconds (default: no expiry)
+    #[arg(long, value_name = "SECONDS", requires = "cache")]
+    cache_ttl: Option<f64>,
+    /// Override the response body's charset, instead of using Content-Type
+    #[arg(long, value_name = "LABEL")]
+    charset: Option<String>,
+    /// Verify the downloaded --output file against a digest (sha1, sha256 or sha512)
+    #[arg(long, value_name = "ALGO:HEX", requires = "output")]
+    checksum: Option<String>,
+    /// Present a client certificate (PEM) for mutual TLS
+    #[arg(long, value_name = "FILE", requires = "client_key")]
+    client_cert: Option<PathBuf>,
+    /// Private key (PEM) matching --client-cert
+    #[arg(long, value_name = "FILE", requires = "client_cert")]
+    client_key: Option<PathBuf>,
+    /// Configure when to output styled/colored text
+    #[arg(long, value_name = "MODE", default_value = "auto")]
+    color: ColorMode,
+    /// Resume a partial download, appending to the file given by --output
+    #[arg(long = "continue", requires = "output")]
+    continue_download: bool,
+    /// Load/store cookies from a Netscape-format cookies.txt file
+    #[arg(long, value_name = "FILE")]
+    cookie_jar: Option<PathBuf>,
     /// Send a request body
     #[arg(short, long, group = "body", value_name = "[@]VALUE")]
     data: Option<String>,
@@ -52,6 +95,9 @@ struct Cli {
     /// Use an editor to send a request body
     #[arg(short, long)]
     edit: bool,
+    /// Wait for a 100-continue response before sending the request body
+    #[arg(long)]
+    expect_continue: bool,
     /// Send a urlencoded form body
     #[arg(short, long, group = "body", value_name = "KEY=VALUE")]
     #[arg(conflicts_with = "data", conflicts_with = "multipart")]
@@ -62,6 +108,9 @@ struct Cli {
     /// Force the use of an HTTP version
     #[arg(long, value_name = "VERSION")]
     http: Option<Http>,
+    /// Force a specific protocol for rendering images in the terminal
+    #[arg(long, value_name = "MODE", default_value = "auto")]
+    image_protocol: ImageProtocol,
     /// Accept invalid TLS certificates (DANGEROUS!)
     #[arg(long)]
     insecure: bool,
@@ -69,6 +118,9 @@ struct Cli {
     #[arg(short, long, conflicts_with = "xml")]
     #[arg(conflicts_with = "form", conflicts_with = "multipart")]
     json: bool,
+    /// Maximum number of redirects to follow (0 disables following)
+    #[arg(long, value_name = "NUM", default_value_t = 10)]
+    max_redirects: u32,
     /// HTTP method to use
     #[arg(short, long)]
     method: Option<String>,
@@ -76,18 +128,35 @@ struct Cli {
     #[arg(short = 'F', long, value_name = "NAME=[@]VALUE")]
     #[arg(conflicts_with = "data", conflicts_with = "form")]
     multipart: Vec<String>,
+    /// Don't decode a compressed response body, dumping the raw bytes instead
+    #[arg(long)]
+    no_decompress: bool,
     /// Avoid using a pager for displaying the response body
     #[arg(long)]
     no_pager: bool,
     /// Write the response body to a file
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
+    /// Download the output file using N concurrent range requests
+    #[arg(long, value_name = "N", requires = "output", conflicts_with = "continue_download")]
+    parallel: Option<u32>,
     /// Configure a proxy
     #[arg(long)]
     proxy: Option<String>,
     /// Append query parameters to the url
     #[arg(short, long, value_name = "KEY=VALUE")]
     query: Vec<String>,
+    /// Number of additional attempts on connection errors and 408/429/5xx responses
+    #[arg(long, value_name = "COUNT", default_value_t = 0)]
+    retry: u32,
+    /// Overall wall-clock deadline across all retry attempts, in seconds
+    #[arg(long, value_name = "SECONDS")]
+    retry_max_time: Option<f64>,
+    /// Sign the request using HTTP Message Signatures
+    #[arg(long, value_name = "KEYID:ALG:SECRET")]
+    #[arg(conflicts_with = "aws_sigv4", conflicts_with = "basic")]
+    #[arg(conflicts_with = "bearer")]
+    signature: Option<String>,
     /// Avoid printing anything to stderr
     #[arg(short, long)]
     silent: bool,
@@ -106,14 +175,31 @@ struct Cli {
     xml: bool,
 }
 
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub(crate) enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub(crate) enum ImageProtocol {
+    Auto,
+    Kitty,
+    #[value(name = "iterm2")]
+    Iterm2,
+    Sixel,
+    None,
+}
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
 enum Http {
     #[value(name = "1")]
     One,
     #[value(name = "2")]
     Two,
-    // #[value(name = "3")]
-    // Three,
+    #[value(name = "3")]
+    Three,
 }
 
 impl Deref for Http {
@@ -5,7 +5,7 @@ use std::{
 
 use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::fetch::IS_STDERR_TTY;
+use crate::{checksum::Hasher, fetch::IS_STDERR_TTY};
 
 pub(crate) struct ProgressReader<R> {
     inner: R,
@@ -15,6 +15,13 @@ pub(crate) struct ProgressReader<R> {
 
 impl<R> ProgressReader<R> {
     pub(crate) fn new(r: R, size: Option<u64>, hidden: bool) -> Self {
+        Self::with_start(r, size, hidden, 0)
+    }
+
+    // Like `new`, but the bar/spinner starts already advanced by `start`
+    // bytes, for resumed downloads where `size` is the full file size but
+    // the reader itself only yields the remaining bytes.
+    pub(crate) fn with_start(r: R, size: Option<u64>, hidden: bool, start: u64) -> Self {
         if *IS_STDERR_TTY {
             console::set_colors_enabled(true);
         }
@@ -36,6 +43,9 @@ impl<R> ProgressReader<R> {
             progress.enable_steady_tick(Duration::from_millis(100));
             progress
         };
+        if start > 0 {
+            progress.set_position(start);
+        }
 
         // Call tick to print the progress bar to stderr.
         progress.tick();
@@ -70,3 +80,29 @@ impl<R> Drop for ProgressReader<R> {
         }
     }
 }
+
+/// Feeds every byte read through a digest, so the final hex digest is ready
+/// as soon as the wrapped reader has been fully consumed (e.g. by
+/// `io::copy`), for comparison against a `--checksum` value.
+pub(crate) struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R> HashingReader<R> {
+    pub(crate) fn new(r: R, hasher: Hasher) -> Self {
+        Self { inner: r, hasher }
+    }
+
+    pub(crate) fn finalize_hex(self) -> String {
+        self.hasher.finalize_hex()
+    }
+}
+
+impl<R: io::Read> io::Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.hasher.update(&buf[..len]);
+        Ok(len)
+    }
+}
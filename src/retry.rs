@@ -0,0 +1,135 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher as _},
+    thread,
+    time::{Duration, Instant},
+};
+
+use jiff::{fmt::rfc2822, Timestamp};
+use reqwest::{header::HeaderMap, StatusCode};
+
+use crate::{error::Error, http};
+
+static BASE_BACKOFF: Duration = Duration::from_millis(250);
+static MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// `--retry`/`--retry-max-time`: how many extra attempts to make beyond the
+/// first, and the wall-clock deadline across all of them combined.
+pub(crate) struct RetryPolicy {
+    retries: u32,
+    deadline: Option<Instant>,
+}
+
+impl RetryPolicy {
+    pub(crate) fn new(retries: u32, max_time: Option<Duration>) -> Self {
+        Self {
+            // The deadline is computed once, here, rather than per attempt,
+            // so a slow server can't reset the clock by responding just
+            // before each attempt's own --timeout expires.
+            retries,
+            deadline: max_time.map(|d| Instant::now() + d),
+        }
+    }
+}
+
+/// Sends `req`, retrying on connection/timeout errors and on 408/429/5xx
+/// responses, honoring a `Retry-After` header when present and otherwise
+/// backing off exponentially with jitter. No attempt is made, or waited on,
+/// past the policy's overall deadline.
+///
+/// A request carrying a non-replayable streaming body (e.g. a file that
+/// can't be cloned and re-sent) is attempted once with no retries; `quiet`
+/// suppresses the diagnostic noting why.
+pub(crate) fn send_with_retry(
+    req: http::Request,
+    policy: &RetryPolicy,
+    quiet: bool,
+) -> Result<http::Response, Error> {
+    let mut attempt = 0;
+    let mut current = req;
+    loop {
+        let can_retry = attempt < policy.retries;
+        let next = if can_retry { current.try_clone() } else { None };
+        if can_retry && next.is_none() && current.has_body() && !quiet {
+            eprintln!("warning: retries disabled, request body cannot be replayed");
+        }
+
+        match current.send() {
+            Ok(res) if !is_retryable_status(res.status()) => return Ok(res),
+            Ok(res) => {
+                let Some(next) = next else { return Ok(res) };
+                let wanted = retry_after(res.headers()).unwrap_or_else(|| backoff(attempt));
+                match remaining_delay(policy, wanted) {
+                    Some(delay) => {
+                        thread::sleep(delay);
+                        current = next;
+                        attempt += 1;
+                    }
+                    None => return Ok(res),
+                }
+            }
+            Err(err) => {
+                let Some(next) = next else { return Err(err) };
+                match remaining_delay(policy, backoff(attempt)) {
+                    Some(delay) => {
+                        thread::sleep(delay);
+                        current = next;
+                        attempt += 1;
+                    }
+                    None => return Err(err),
+                }
+            }
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status.is_server_error()
+}
+
+// Caps `wanted` to whatever's left before the policy's overall deadline,
+// or `None` if the deadline has already passed (the caller should give up
+// rather than sleep and retry).
+fn remaining_delay(policy: &RetryPolicy, wanted: Duration) -> Option<Duration> {
+    match policy.deadline {
+        None => Some(wanted),
+        Some(deadline) => {
+            let now = Instant::now();
+            if now >= deadline {
+                None
+            } else {
+                Some(wanted.min(deadline - now))
+            }
+        }
+    }
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let when = rfc2822::parse(value).ok()?.timestamp();
+    let remaining = when.duration_since(Timestamp::now()).as_secs_f64();
+    Some(Duration::from_secs_f64(remaining.max(0.0)))
+}
+
+// `base * 2^attempt`, capped, with up to 50% jitter added so concurrent
+// clients retrying the same failure don't all land on the same instant.
+fn backoff(attempt: u32) -> Duration {
+    let exp = BASE_BACKOFF
+        .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+        .min(MAX_BACKOFF);
+    exp + exp.mul_f64(0.5 * random_fraction())
+}
+
+// A cheap, non-cryptographic source of jitter: `RandomState` mixes in
+// OS-seeded randomness per build, and an un-written `Hasher`'s `finish()`
+// still reflects that seed, so repeated calls vary without pulling in a
+// `rand` dependency just to jitter a sleep.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits as f64) / (u64::MAX as f64)
+}
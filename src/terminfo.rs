@@ -0,0 +1,202 @@
+// Minimal reader for the compiled terminfo format described in
+// term(5)/terminfo(5), just enough to answer one question: does the
+// current `$TERM` entry advertise 24-bit truecolor support? That is
+// signaled either by the extended boolean capability `Tc` (widely used by
+// tmux/ncurses-aware terminals) or `RGB` (used by some newer terminfo
+// databases), or by a numeric `colors` capability of at least 0x1000000.
+
+use std::{env, fs, path::PathBuf};
+
+const MAGIC_16BIT: i16 = 0x011A;
+const MAGIC_32BIT: i16 = 0x021E;
+
+/// Probes the compiled terminfo entry for `$TERM` for truecolor support.
+/// Returns `false` if `$TERM` is unset or no matching/parsable entry is
+/// found.
+pub(crate) fn supports_truecolor() -> bool {
+    let term = match env::var("TERM") {
+        Ok(term) if !term.is_empty() => term,
+        _ => return false,
+    };
+    find_terminfo_file(&term)
+        .and_then(|path| fs::read(path).ok())
+        .is_some_and(|data| parse_truecolor(&data))
+}
+
+fn find_terminfo_file(term: &str) -> Option<PathBuf> {
+    let first = term.chars().next()?;
+
+    let mut dirs = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(raw) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&raw));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    dirs.push(PathBuf::from("/usr/share/terminfo"));
+    dirs.push(PathBuf::from("/lib/terminfo"));
+    dirs.push(PathBuf::from("/etc/terminfo"));
+
+    dirs.into_iter().find_map(|dir| {
+        // Most systems lay terminfo entries out as <dir>/<first-letter>/<name>.
+        let by_letter = dir.join(first.to_string()).join(term);
+        if by_letter.is_file() {
+            return Some(by_letter);
+        }
+        // Some systems (notably Darwin) use <dir>/<first-byte-in-hex>/<name>.
+        let by_hex = dir.join(format!("{:02x}", first as u32)).join(term);
+        by_hex.is_file().then_some(by_hex)
+    })
+}
+
+fn parse_truecolor(data: &[u8]) -> bool {
+    let mut r = Reader::new(data);
+
+    let magic = r.read_i16();
+    let number_size = match magic {
+        Some(MAGIC_32BIT) => 4,
+        Some(MAGIC_16BIT) => 2,
+        _ => return false,
+    };
+
+    let Some(name_size) = r.read_i16().map(|v| v as usize) else {
+        return false;
+    };
+    let Some(bool_count) = r.read_i16().map(|v| v as usize) else {
+        return false;
+    };
+    let Some(num_count) = r.read_i16().map(|v| v as usize) else {
+        return false;
+    };
+    let Some(str_count) = r.read_i16().map(|v| v as usize) else {
+        return false;
+    };
+    let Some(str_size) = r.read_i16().map(|v| v as usize) else {
+        return false;
+    };
+
+    if r.skip(name_size).is_none() || r.skip(bool_count).is_none() {
+        return false;
+    }
+    if (name_size + bool_count) % 2 != 0 && r.skip(1).is_none() {
+        return false;
+    }
+    let Some(numbers) = r.read_numbers(num_count, number_size) else {
+        return false;
+    };
+    if r.skip(str_count * 2).is_none() || r.skip(str_size).is_none() {
+        return false;
+    }
+
+    // `colors` is the standard numeric capability index 13; some terminals
+    // report a huge value here to signal full 24-bit support.
+    const COLORS_INDEX: usize = 13;
+    if numbers.get(COLORS_INDEX).is_some_and(|&v| v >= 0x1000000) {
+        return true;
+    }
+
+    parse_extended_truecolor(&mut r, number_size).unwrap_or(false)
+}
+
+fn parse_extended_truecolor(r: &mut Reader, number_size: usize) -> Option<bool> {
+    // The extended section has its own 5-field header.
+    let ext_bool_count = r.read_i16()? as usize;
+    let ext_num_count = r.read_i16()? as usize;
+    let ext_str_count = r.read_i16()? as usize;
+    let ext_str_size = r.read_i16()? as usize;
+    let _last_str_offset = r.read_i16()?;
+
+    let bools = r.read_bytes(ext_bool_count)?.to_vec();
+    if ext_bool_count % 2 != 0 {
+        r.skip(1)?;
+    }
+    let numbers = r.read_numbers(ext_num_count, number_size)?;
+
+    // Offsets into the string table: first for the ext_str_count string
+    // capability values, then one per bool/number/string capability *name*.
+    let value_count = ext_str_count + ext_bool_count + ext_num_count + ext_str_count;
+    let offsets = r.read_numbers(value_count, 2)?;
+    let str_table = r.read_bytes(ext_str_size)?;
+
+    let name_offsets = &offsets[ext_str_count..];
+    let names: Vec<&str> = name_offsets
+        .iter()
+        .filter_map(|&off| read_cstr(str_table, off))
+        .collect();
+
+    for (i, &name) in names.iter().enumerate() {
+        if i < ext_bool_count && (name == "Tc" || name == "RGB") {
+            return Some(bools.get(i).copied().unwrap_or(0) != 0);
+        }
+        if name == "colors" {
+            let num_index = i.checked_sub(ext_bool_count)?;
+            if numbers.get(num_index).is_some_and(|&v| v >= 0x1000000) {
+                return Some(true);
+            }
+        }
+    }
+
+    Some(false)
+}
+
+fn read_cstr(data: &[u8], offset: i32) -> Option<&str> {
+    let offset = usize::try_from(offset).ok()?;
+    let rest = data.get(offset..)?;
+    let end = rest.iter().position(|&b| b == 0)?;
+    std::str::from_utf8(&rest[..end]).ok()
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn read_i16(&mut self) -> Option<i16> {
+        let bytes = self.read_bytes(2)?;
+        Some(i16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(n)?;
+        let out = self.data.get(self.pos..end)?;
+        self.pos = end;
+        Some(out)
+    }
+
+    fn skip(&mut self, n: usize) -> Option<()> {
+        self.read_bytes(n).map(|_| ())
+    }
+
+    fn read_numbers(&mut self, count: usize, size: usize) -> Option<Vec<i32>> {
+        let mut out = Vec::with_capacity(count);
+        for _ in 0..count {
+            let bytes = self.read_bytes(size)?;
+            let v = if size == 4 {
+                i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            } else {
+                i16::from_le_bytes([bytes[0], bytes[1]]) as i32
+            };
+            out.push(v);
+        }
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_truecolor_rejects_garbage() {
+        assert!(!parse_truecolor(&[]));
+        assert!(!parse_truecolor(&[0, 0, 0, 0]));
+    }
+}
@@ -109,9 +109,81 @@ fn str_to_color(input: &str) -> Option<anstyle::Color> {
         return Some(anstyle::Color::Ansi(ansi));
     }
 
+    if let Some(rgb) = from_hex_color(input) {
+        return Some(anstyle::Color::Rgb(rgb));
+    }
+
+    if let Some(rgb) = from_rgb_fn_color(input) {
+        return Some(anstyle::Color::Rgb(rgb));
+    }
+
     None
 }
 
+/// Parses `#rrggbb` or the shorthand `#rgb` form into an `anstyle::RgbColor`.
+fn from_hex_color(input: &str) -> Option<anstyle::RgbColor> {
+    let hex = input.strip_prefix('#')?;
+    let (r, g, b) = match hex.len() {
+        6 => (&hex[0..2], &hex[2..4], &hex[4..6]),
+        3 => {
+            // Expand each digit, e.g. "f90" -> "ff9900".
+            return Some(anstyle::RgbColor(
+                u8::from_str_radix(&hex[0..1].repeat(2), 16).ok()?,
+                u8::from_str_radix(&hex[1..2].repeat(2), 16).ok()?,
+                u8::from_str_radix(&hex[2..3].repeat(2), 16).ok()?,
+            ));
+        }
+        _ => return None,
+    };
+    Some(anstyle::RgbColor(
+        u8::from_str_radix(r, 16).ok()?,
+        u8::from_str_radix(g, 16).ok()?,
+        u8::from_str_radix(b, 16).ok()?,
+    ))
+}
+
+/// Parses the `rgb(r, g, b)` notation common in CSS/editor color schemes.
+fn from_rgb_fn_color(input: &str) -> Option<anstyle::RgbColor> {
+    let inner = input
+        .strip_prefix("rgb(")?
+        .strip_suffix(')')?
+        .trim();
+    let mut parts = inner.split(',').map(|v| v.trim().parse::<u8>());
+    let r = parts.next()?.ok()?;
+    let g = parts.next()?.ok()?;
+    let b = parts.next()?.ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(anstyle::RgbColor(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_to_color_hex() {
+        assert_eq!(
+            str_to_color("#1a2b3c"),
+            Some(anstyle::Color::Rgb(anstyle::RgbColor(0x1a, 0x2b, 0x3c)))
+        );
+        assert_eq!(
+            str_to_color("#fff"),
+            Some(anstyle::Color::Rgb(anstyle::RgbColor(0xff, 0xff, 0xff)))
+        );
+    }
+
+    #[test]
+    fn test_str_to_color_rgb_fn() {
+        assert_eq!(
+            str_to_color("rgb(10, 20, 30)"),
+            Some(anstyle::Color::Rgb(anstyle::RgbColor(10, 20, 30)))
+        );
+        assert_eq!(str_to_color("rgb(10, 20)"), None);
+    }
+}
+
 fn from_ansi_color(input: &str) -> Option<anstyle::AnsiColor> {
     match input {
         "black" => Some(anstyle::AnsiColor::Black),
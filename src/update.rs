@@ -8,11 +8,15 @@ use std::{
 
 use reqwest::blocking::{Client, ClientBuilder};
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
 
 static TARGET: &str = env!("TARGET");
 static VERSION: &str = env!("CARGO_PKG_VERSION");
 static APP_STRING: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+// A generous ceiling on the release artifact size, just to bound how much
+// gets buffered in memory for the pre-unpack checksum check.
+static MAX_ARTIFACT_SIZE: u64 = 256 * 1024 * 1024;
 
 type Error = Box<dyn std::error::Error>;
 
@@ -84,17 +88,52 @@ fn get_latest_tag(client: &Client) -> Result<String, Error> {
 }
 
 fn get_artifact_reader(client: &Client, tag: &str) -> Result<impl Read, Error> {
-    let url = format!(
-        "https://github.com/ryanfowler/fetch/releases/download/{tag}/fetch-{tag}-{TARGET}.tar.gz"
-    );
+    let name = format!("fetch-{tag}-{TARGET}.tar.gz");
+    let url = format!("https://github.com/ryanfowler/fetch/releases/download/{tag}/{name}");
     let res = client.get(url).send()?;
 
     let status = res.status();
     if status != 200 {
-        Err(format!("downloading artifact: received status {status}").into())
-    } else {
-        Ok(res)
+        return Err(format!("downloading artifact: received status {status}").into());
+    }
+
+    // Buffer the full artifact so its checksum can be verified before any
+    // of it is unpacked onto disk, rather than trusting a corrupted or
+    // tampered download as soon as the network hands it over.
+    let mut artifact = Vec::new();
+    res.take(MAX_ARTIFACT_SIZE).read_to_end(&mut artifact)?;
+    verify_checksum(client, tag, &name, &artifact)?;
+
+    Ok(io::Cursor::new(artifact))
+}
+
+// Fetches the checksum file published alongside the release artifact (a
+// bare lowercase-hex sha256 digest, as produced by `sha256sum`) and compares
+// it against one computed locally over the downloaded bytes.
+fn verify_checksum(client: &Client, tag: &str, name: &str, artifact: &[u8]) -> Result<(), Error> {
+    let url = format!("https://github.com/ryanfowler/fetch/releases/download/{tag}/{name}.sha256");
+    let res = client.get(url).send()?;
+
+    let status = res.status();
+    if status != 200 {
+        return Err(format!("downloading checksum: received status {status}").into());
+    }
+
+    let body = res.text()?;
+    let expected = body
+        .split_whitespace()
+        .next()
+        .ok_or("checksum file is empty")?
+        .to_ascii_lowercase();
+
+    let mut hasher = Sha256::new();
+    hasher.update(artifact);
+    let actual: String = hasher.finalize().iter().map(|b| format!("{b:02x}")).collect();
+
+    if actual != expected {
+        return Err(format!("checksum mismatch: expected {expected}, got {actual}").into());
     }
+    Ok(())
 }
 
 #[cfg(not(target_os = "windows"))]
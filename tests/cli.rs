@@ -1,3 +1,5 @@
+use std::{fs, io::Write};
+
 use assert_cmd::Command;
 use mockito::Server;
 use serde::Deserialize;
@@ -110,6 +112,193 @@ fn test_dry_run() {
     cmd.assert().success();
 }
 
+/// Test that `--continue` resumes a partially-downloaded file: the
+/// existing bytes on disk should be sent as the `Range` start, and the
+/// response body appended rather than overwriting the file.
+#[test]
+fn test_continue_resumes_download() {
+    let partial = b"hello, ";
+    let rest = b"world!";
+
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/file")
+        .match_header("range", format!("bytes={}-", partial.len()).as_str())
+        .with_status(206)
+        .with_header(
+            "content-range",
+            &format!(
+                "bytes {}-{}/{}",
+                partial.len(),
+                partial.len() + rest.len() - 1,
+                partial.len() + rest.len()
+            ),
+        )
+        .with_body(rest)
+        .create();
+
+    let url = format!("{}/file", server.url());
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("download.txt");
+    fs::File::create(&output)
+        .unwrap()
+        .write_all(partial)
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("fetch").unwrap();
+    cmd.arg("--continue")
+        .arg("--output")
+        .arg(&output)
+        .arg(url);
+    cmd.assert().success();
+
+    let contents = fs::read(&output).unwrap();
+    assert_eq!(contents, b"hello, world!");
+
+    mock.assert();
+}
+
+/// Test that `--continue --checksum` verifies the digest of the *whole*
+/// resumed file (bytes already on disk plus the newly-appended tail), not
+/// just the newly-streamed portion.
+#[test]
+fn test_continue_verifies_checksum_of_whole_file() {
+    use sha2::{Digest, Sha256};
+
+    let partial = b"hello, ";
+    let rest = b"world!";
+
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/file")
+        .match_header("range", format!("bytes={}-", partial.len()).as_str())
+        .with_status(206)
+        .with_header(
+            "content-range",
+            &format!(
+                "bytes {}-{}/{}",
+                partial.len(),
+                partial.len() + rest.len() - 1,
+                partial.len() + rest.len()
+            ),
+        )
+        .with_body(rest)
+        .create();
+
+    let url = format!("{}/file", server.url());
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("download.txt");
+    fs::File::create(&output)
+        .unwrap()
+        .write_all(partial)
+        .unwrap();
+
+    let mut hasher = Sha256::new();
+    hasher.update(partial);
+    hasher.update(rest);
+    let digest = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+
+    let mut cmd = Command::cargo_bin("fetch").unwrap();
+    cmd.arg("--continue")
+        .arg("--output")
+        .arg(&output)
+        .arg("--checksum")
+        .arg(format!("sha256:{digest}"))
+        .arg(url);
+    cmd.assert().success();
+
+    let contents = fs::read(&output).unwrap();
+    assert_eq!(contents, b"hello, world!");
+
+    mock.assert();
+}
+
+/// Test that `--cache` stores a response's `ETag` and replays the cached
+/// body when a later request to the same URL gets a `304 Not Modified`.
+#[test]
+fn test_cache_revalidates_with_etag() {
+    let etag = "\"abc123\"";
+    let body = r#"{"message": "hello"}"#;
+
+    let mut server = Server::new();
+    let first_mock = server
+        .mock("GET", "/cached")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("etag", etag)
+        .with_body(body)
+        .create();
+
+    let url = format!("{}/cached", server.url());
+    let cache_home = tempfile::tempdir().unwrap();
+
+    let mut cmd = Command::cargo_bin("fetch").unwrap();
+    cmd.env("XDG_CACHE_HOME", cache_home.path())
+        .arg("--cache")
+        .arg(&url);
+    let assert = cmd.assert().success();
+    assert_eq!(assert.get_output().stdout, body.as_bytes());
+    first_mock.assert();
+
+    let second_mock = server
+        .mock("GET", "/cached")
+        .match_header("if-none-match", etag)
+        .with_status(304)
+        .create();
+
+    let mut cmd = Command::cargo_bin("fetch").unwrap();
+    cmd.env("XDG_CACHE_HOME", cache_home.path())
+        .arg("--cache")
+        .arg(&url);
+    let assert = cmd.assert().success();
+    assert_eq!(assert.get_output().stdout, body.as_bytes());
+    second_mock.assert();
+}
+
+/// Test that a cross-origin redirect doesn't leak the `x-amz-security-token`
+/// (or any other `x-amz-*` SigV4 header) to the redirect target.
+#[test]
+fn test_redirect_strips_amz_headers_cross_origin() {
+    let mut origin_server = Server::new();
+    let mut other_server = Server::new();
+
+    let redirect_location = format!("{}/dest", other_server.url());
+    let redirect_mock = origin_server
+        .mock("GET", "/src")
+        .match_header("x-amz-security-token", "SESSION_TOKEN")
+        .with_status(302)
+        .with_header("location", &redirect_location)
+        .create();
+
+    let dest_mock = other_server
+        .mock("GET", "/dest")
+        .match_header("x-amz-security-token", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body("ok")
+        .create();
+
+    let url = format!("{}/src", origin_server.url());
+
+    let mut cmd = Command::cargo_bin("fetch").unwrap();
+    cmd.env("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE")
+        .env("AWS_SECRET_ACCESS_KEY", "secret")
+        .env("AWS_SESSION_TOKEN", "SESSION_TOKEN")
+        .arg("--aws-sigv4")
+        .arg("us-east-1/execute-api")
+        .arg(url);
+    let assert = cmd.assert().success();
+    assert_eq!(assert.get_output().stdout, b"ok");
+
+    redirect_mock.assert();
+    dest_mock.assert();
+}
+
 /// Test error handling by providing an invalid URL scheme.
 /// The CLI should fail and emit an error message.
 #[test]